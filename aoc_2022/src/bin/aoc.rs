@@ -0,0 +1,6 @@
+use aoc_commons as aoc;
+use aoc::anyhow;
+
+fn main() -> anyhow::Result<()> {
+	aoc::puzzle::main(&aoc2022::registry())
+}
@@ -0,0 +1,63 @@
+use aoc_commons::puzzle::Registry;
+
+mod puzzle01;
+mod puzzle02;
+mod puzzle03;
+mod puzzle04;
+mod puzzle05;
+mod puzzle06;
+mod puzzle07;
+mod puzzle08;
+mod puzzle09;
+mod puzzle10;
+mod puzzle11;
+mod puzzle12;
+mod puzzle13;
+mod puzzle14;
+mod puzzle15;
+mod puzzle16;
+mod puzzle17;
+mod puzzle18;
+mod puzzle19;
+mod puzzle20;
+mod puzzle21;
+mod puzzle22;
+mod puzzle23;
+mod puzzle24;
+mod puzzle25;
+
+/// Registers all days of 2022.
+pub fn register(registry: &mut Registry) {
+	puzzle01::register(registry);
+	puzzle02::register(registry);
+	puzzle03::register(registry);
+	puzzle04::register(registry);
+	puzzle05::register(registry);
+	puzzle06::register(registry);
+	puzzle07::register(registry);
+	puzzle08::register(registry);
+	puzzle09::register(registry);
+	puzzle10::register(registry);
+	puzzle11::register(registry);
+	puzzle12::register(registry);
+	puzzle13::register(registry);
+	puzzle14::register(registry);
+	puzzle15::register(registry);
+	puzzle16::register(registry);
+	puzzle17::register(registry);
+	puzzle18::register(registry);
+	puzzle19::register(registry);
+	puzzle20::register(registry);
+	puzzle21::register(registry);
+	puzzle22::register(registry);
+	puzzle23::register(registry);
+	puzzle24::register(registry);
+	puzzle25::register(registry);
+}
+
+pub fn registry() -> Registry {
+	let mut registry = Registry::new();
+	register(&mut registry);
+
+	registry
+}
@@ -0,0 +1,51 @@
+use std::fmt::Display;
+
+use anyhow::Context;
+
+use aoc_commons as aoc;
+use aoc::{anyhow, log, puzzle::{Puzzle, Registry}};
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 1;
+
+	/// Inventory sums sorted from the largest.
+	type Input<'a> = Vec<u32>;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut all_inventories = Vec::new();
+		for inventory_str in input.split("\n\n") {
+			let mut one_inventory = Vec::new();
+			for item_str in inventory_str.split('\n').filter(|s| !s.is_empty()) {
+				let calories = item_str.parse::<u32>().context("Failed to parse input item")?;
+
+				one_inventory.push(calories);
+			}
+
+			all_inventories.push(one_inventory);
+		}
+
+		let mut inventory_sums: Vec<u32> = all_inventories.iter().map(
+			|inv| -> u32 { inv.iter().sum() }
+		).collect();
+		inventory_sums.sort_by(|x, y| x.cmp(y).reverse());
+
+		Ok(inventory_sums)
+	}
+
+	fn part1(inventory_sums: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		inventory_sums.first().copied().context("No inventories")
+	}
+
+	fn part2(inventory_sums: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		anyhow::ensure!(inventory_sums.len() >= 3, "Fewer than three inventories");
+		log::debug!("top three calories: {:?}", &inventory_sums[..3]);
+
+		Ok(inventory_sums[..3].iter().sum::<u32>())
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
+}
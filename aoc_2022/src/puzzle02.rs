@@ -1,9 +1,9 @@
-use std::io::Read;
+use std::fmt::Display;
 
 use anyhow::Context;
 
 use aoc_commons as aoc;
-use aoc::anyhow;
+use aoc::{anyhow, puzzle::{Puzzle, Registry}};
 
 #[derive(Debug)]
 enum GamePlay {
@@ -88,30 +88,47 @@ fn play(them: &GamePlay, us: &GamePlay) -> GameOutcome {
 	}
 }
 
-fn main() -> anyhow::Result<()> {
-	let mut file = aoc::initialize()?;
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 2;
 
-	let mut input = String::new();
-	file.read_to_string(&mut input).context("Failed to read input file")?;
+	/// Their play, our play and the outcome read from the second column.
+	type Input<'a> = Vec<(GamePlay, GamePlay, GameOutcome)>;
 
-	let mut total_score = 0;
-	let mut total_score2 = 0;
-	for round_str in input.split('\n').filter(|s| !s.is_empty()) {
-		let (them_str, us_str) = round_str.split_once(" ").context("Failed to split round row by space")?;
-		let them = GamePlay::from(them_str);
-		let us = GamePlay::from(us_str);
-		let outcome = play(&them, &us);
-		total_score += us.score() + outcome.score();
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut rounds = Vec::new();
+		for round_str in input.split('\n').filter(|s| !s.is_empty()) {
+			let (them_str, us_str) = round_str.split_once(" ").context("Failed to split round row by space")?;
+			rounds.push((GamePlay::from(them_str), GamePlay::from(us_str), GameOutcome::from(us_str)));
+		}
 
-		let outcome2 = GameOutcome::from(us_str);
-		let us2 = them.select_by_outcome(&outcome2);
-		total_score2 += us2.score() + outcome2.score();
+		Ok(rounds)
+	}
 
-		// log::debug!("round: {} - them = {:?}, us = {:?}, outcome = {:?}, score = {}", round_str, them, us2, outcome2, us2.score() + outcome2.score());
+	fn part1(rounds: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut total_score = 0;
+		for (them, us, _) in rounds {
+			let outcome = play(them, us);
+			total_score += us.score() + outcome.score();
+		}
+
+		Ok(total_score)
 	}
 
-	println!("Total score: {}", total_score);
-	println!("Total score 2: {}", total_score2);
+	fn part2(rounds: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut total_score2 = 0;
+		for (them, _, outcome2) in rounds {
+			let us2 = them.select_by_outcome(outcome2);
+			total_score2 += us2.score() + outcome2.score();
+
+			// log::debug!("round: them = {:?}, us = {:?}, outcome = {:?}, score = {}", them, us2, outcome2, us2.score() + outcome2.score());
+		}
+
+		Ok(total_score2)
+	}
+}
 
-	Ok(())
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
 }
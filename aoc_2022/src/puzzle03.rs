@@ -0,0 +1,70 @@
+use std::{collections::HashSet, fmt::Display};
+
+use anyhow::Context;
+
+use aoc_commons as aoc;
+use aoc::{anyhow, puzzle::{Puzzle, Registry}};
+
+fn priority(item: char) -> anyhow::Result<u32> {
+	anyhow::ensure!((item as u32) < 256, "Invalid item value");
+
+	if item.is_ascii_lowercase() {
+		Ok(item as u32 - 'a' as u32 + 1)
+	} else if item.is_ascii_uppercase() {
+		Ok(item as u32 - 'A' as u32 + 27)
+	} else {
+		anyhow::bail!("Invalid item {}", item)
+	}
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 3;
+
+	/// Items of the left and right compartment of each rucksack.
+	type Input<'a> = Vec<(HashSet<char>, HashSet<char>)>;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut all_rucksacks = Vec::new();
+		for contents_str in input.split('\n').filter(|s| !s.is_empty()) {
+			let (left_str, right_str) = contents_str.split_at(contents_str.len() / 2);
+
+			all_rucksacks.push((left_str.chars().collect(), right_str.chars().collect()));
+		}
+
+		Ok(all_rucksacks)
+	}
+
+	fn part1(all_rucksacks: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut total_priority = 0;
+		for (left_items, right_items) in all_rucksacks {
+			total_priority += left_items
+				.intersection(right_items)
+				.map(|i| priority(*i))
+				.try_fold(0, |acc, res| res.map(|curr| acc + curr))
+			?;
+		}
+
+		Ok(total_priority)
+	}
+
+	fn part2(all_rucksacks: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let all_rucksacks: Vec<HashSet<char>> = all_rucksacks.iter().map(
+			|(left_items, right_items)| left_items.union(right_items).copied().collect()
+		).collect();
+
+		let mut total_trio_priority = 0;
+		for group in all_rucksacks.chunks(3) {
+			anyhow::ensure!(group.len() == 3, "Incomplete group");
+			let intersection = group[0].intersection(&group[1]).filter(|item| group[2].contains(item)).copied().nth(0).context("group intersection is empty")?;
+			total_trio_priority += priority(intersection)?;
+		}
+
+		Ok(total_trio_priority)
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
+}
@@ -0,0 +1,56 @@
+use std::{fmt::Display, ops::RangeInclusive};
+
+use anyhow::Context;
+
+use aoc_commons as aoc;
+use aoc::{anyhow, puzzle::{Puzzle, Registry}};
+
+fn parse_range(value: &str) -> anyhow::Result<RangeInclusive<u32>> {
+	let (start_str, end_str) = value.split_once("-").context("Failed to split range by -")?;
+
+	let start: u32 = start_str.parse().context("Failed to parse start number")?;
+	let end: u32 = end_str.parse().context("Failed to parse end number")?;
+
+	Ok(start ..= end)
+}
+
+fn partial_overlap(left: &RangeInclusive<u32>, right: &RangeInclusive<u32>) -> bool {
+	(left.start() >= right.start() && left.start() <= right.end())
+	|| (right.start() >= left.start() && right.start() <= left.end())
+}
+
+fn full_overlap(left: &RangeInclusive<u32>, right: &RangeInclusive<u32>) -> bool {
+	(left.start() >= right.start() && left.end() <= right.end())
+	|| (right.start() >= left.start() && right.end() <= left.end())
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 4;
+
+	type Input<'a> = Vec<(RangeInclusive<u32>, RangeInclusive<u32>)>;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut pairs = Vec::new();
+		for pair_str in input.split('\n').filter(|s| !s.is_empty()) {
+			let (left_str, right_str) = pair_str.split_once(',').context("Failed to split pair by ,")?;
+
+			pairs.push((parse_range(left_str)?, parse_range(right_str)?));
+		}
+
+		Ok(pairs)
+	}
+
+	fn part1(pairs: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		Ok(pairs.iter().filter(|(left, right)| full_overlap(left, right)).count())
+	}
+
+	fn part2(pairs: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		Ok(pairs.iter().filter(|(left, right)| partial_overlap(left, right)).count())
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
+}
@@ -0,0 +1,169 @@
+use std::fmt::{Debug, Display};
+
+use anyhow::Context;
+
+use aoc_commons as aoc;
+use aoc::{anyhow, log, puzzle::{Puzzle, Registry}};
+
+aoc::record! {
+	struct MoveCommand {
+		count: usize,
+		from: usize,
+		to: usize
+	}
+	split ' ' => "move", count, "from", from, "to", to
+}
+impl MoveCommand {
+	pub fn apply(&self, state: &mut State) -> anyhow::Result<()> {
+		let (from, to) = state.get_two_mut(self.from - 1, self.to - 1)?;
+		
+		for _ in 0 .. self.count {
+			to.push(from.pop().context("cannot pop from empty stack")?);
+		}
+
+		Ok(())
+	}
+}
+
+struct MoveCommand2 {
+	count: usize,
+	from: usize,
+	to: usize
+}
+impl From<&MoveCommand> for MoveCommand2 {
+	fn from(other: &MoveCommand) -> Self {
+		Self {
+			count: other.count,
+			from: other.from,
+			to: other.to
+		}
+	}
+}
+impl MoveCommand2 {
+	pub fn apply(&self, state: &mut State) -> anyhow::Result<()> {
+		let (from, to) = state.get_two_mut(self.from - 1, self.to - 1)?;
+
+		to.extend(from.drain(from.len() - self.count ..));
+
+		Ok(())
+	}
+}
+
+
+#[derive(Clone)]
+struct State {
+	stacks: Vec<Vec<char>>
+}
+impl State {
+	pub fn new(count: usize) -> Self {
+		State {
+			stacks: vec![Vec::new(); count]
+		}
+	}
+
+	pub fn get_stacks(&self) -> &[Vec<char>] {
+		&self.stacks
+	}
+
+	pub fn get_two_mut(&mut self, first: usize, second: usize) -> anyhow::Result<(&mut Vec<char>, &mut Vec<char>)> {
+		anyhow::ensure!(first < self.stacks.len(), "first index out of bounds");
+		anyhow::ensure!(second < self.stacks.len(), "second index out of bounds");
+		anyhow::ensure!(first != second, "indices cannot be equal");
+
+		let res = if first < second {
+			let (left, right) = self.stacks.split_at_mut(second);
+			(&mut left[first], &mut right[0])
+		} else {
+			let (left, right) = self.stacks.split_at_mut(first);
+			(&mut right[0], &mut left[second])
+		};
+
+		Ok(res)
+	}
+
+	pub fn push(&mut self, which: usize, value: char) -> anyhow::Result<()> {
+		self.stacks.get_mut(which).context("invalid stack index")?.push(value);
+
+		Ok(())
+	}
+}
+impl Debug for State {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let height = self.stacks.iter().map(|s| s.len()).max().unwrap();
+		let width = self.stacks.len();
+
+		for y_inv in 1 ..= height {
+			for x in 0 .. width {
+				if let Some(value) = self.stacks[x].get(height - y_inv) {
+					write!(f, "[{}] ", value)?;
+				} else {
+					write!(f, "    ")?;
+				}
+			}
+			writeln!(f)?;
+		}
+
+		for c in 1 ..= width {
+			write!(f, " {}  ", c)?;
+		}
+		writeln!(f)?;
+
+		Ok(())
+	}
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 5;
+
+	type Input<'a> = (State, Vec<MoveCommand>);
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let (initial_state_str, procedure_str) = input.split_once("\n\n").context("Failed to split input by \\n\\n")?;
+
+		let (initial_state_str, initial_state_indices_str) = initial_state_str.rsplit_once('\n').context("Failed to split initial state by \\n")?;
+		let last_index: usize = initial_state_indices_str.rsplit_once(' ').context("Failed to split initial state indices by <space>")?.1.parse().context("Failed to parse last column index")?;
+
+		let mut state = State::new(last_index);
+		for state_str in initial_state_str.split('\n').rev() {
+			for x in 0 .. state.get_stacks().len() {
+				let crate_str = &state_str[(x * 4)..][..3];
+				let crate_char = crate_str.chars().nth(1).context("Failed to parse initial state crate")?;
+
+				if crate_char != ' ' {
+					state.push(x, crate_char)?;
+				}
+			}
+		}
+		log::debug!("State:\n{:?}", state);
+
+		let commands = MoveCommand::parse_lines(procedure_str)?;
+
+		Ok((state, commands))
+	}
+
+	fn part1((state, commands): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut state = state.clone();
+		for command in commands {
+			command.apply(&mut state).context("Failed to apply command")?;
+		}
+
+		let top_sequence: String = state.get_stacks().iter().map(|s| s.last().copied().unwrap_or(' ')).collect();
+		Ok(top_sequence)
+	}
+
+	fn part2((state, commands): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut state2 = state.clone();
+		for command in commands {
+			MoveCommand2::from(command).apply(&mut state2).context("Failed to apply command")?;
+		}
+
+		let top_sequence: String = state2.get_stacks().iter().map(|s| s.last().copied().unwrap_or(' ')).collect();
+		Ok(top_sequence)
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
+}
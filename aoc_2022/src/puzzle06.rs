@@ -0,0 +1,43 @@
+use std::{collections::HashSet, fmt::Display};
+
+use aoc_commons as aoc;
+use aoc::{anyhow, puzzle::{Puzzle, Registry}};
+
+// TODO: Should recollect the input string as [char] to be utf8 correct, but we are assuming ascii
+fn find_start_of(sequence: &[u8], size: usize) -> usize {
+	let mut result = 0;
+
+	for (i, window) in sequence.windows(size).enumerate() {
+		let deduplicated: HashSet<&u8> = HashSet::from_iter(window);
+		if deduplicated.len() == size {
+			result = i + window.len();
+			break;
+		}
+	}
+
+	result
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 6;
+
+	type Input<'a> = &'a [u8];
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		Ok(input.trim_end().as_bytes())
+	}
+
+	fn part1(input: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		Ok(find_start_of(input, 4))
+	}
+
+	fn part2(input: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		Ok(find_start_of(input, 14))
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
+}
@@ -0,0 +1,98 @@
+use std::fmt::Display;
+
+use anyhow::Context;
+
+use aoc_commons as aoc;
+use aoc::{anyhow, log, geometry::{Grid2, Point}, puzzle::{Puzzle, Registry}};
+
+type Point2 = Point<2>;
+
+/// Position of `at` relative to the min corner of the map, that is its index in `row` and `column`.
+fn relative(map: &Grid2<i8>, at: Point2) -> (usize, usize) {
+	let min = map.bounding_box().min;
+	((at.x() - min.x()) as usize, (at.y() - min.y()) as usize)
+}
+
+fn is_visible(map: &Grid2<i8>, at: Point2) -> anyhow::Result<bool> {
+	let tree = *map.get(at).context("Invalid tree")?;
+	let (x, y) = relative(map, at);
+	let row = || map.row(at.y()).context("Invalid position");
+	let column = || map.column(at.x()).context("Invalid position");
+	let lower = |other: &i8| *other < tree;
+
+	Ok(
+		row()?.take(x).all(lower) || row()?.skip(x + 1).all(lower)
+			|| column()?.take(y).all(lower) || column()?.skip(y + 1).all(lower)
+	)
+}
+
+/// Number of trees seen along `line`, which starts next to the tree of height `center`.
+fn viewing_distance<'a>(mut line: impl ExactSizeIterator<Item = &'a i8>, center: i8) -> usize {
+	let len = line.len();
+	line.position(|&tree| tree >= center).map(|i| i + 1).unwrap_or(len)
+}
+
+fn evaluate_scenic(map: &Grid2<i8>, at: Point2) -> anyhow::Result<usize> {
+	let center = *map.get(at).context("Invalid center tree")?;
+	let (x, y) = relative(map, at);
+	let row = || map.row(at.y()).context("Invalid position");
+	let column = || map.column(at.x()).context("Invalid position");
+
+	let score = viewing_distance(row()?.take(x).rev(), center)
+		* viewing_distance(row()?.skip(x + 1), center)
+		* viewing_distance(column()?.take(y).rev(), center)
+		* viewing_distance(column()?.skip(y + 1), center);
+
+	Ok(score)
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 8;
+
+	type Input<'a> = Grid2<i8>;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let map = Grid2::parse_with(input, |ch| ch.to_digit(10).map(|height| height as i8).context("Invalid tree height"))?;
+		log::debug!("Map:\n{:?}", map);
+
+		Ok(map)
+	}
+
+	fn part1(map: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut map_visible = Grid2::new_fill(0, map.bounding_box())?;
+		let mut total_visible = 0;
+		for y in map.y_range() {
+			for x in map.x_range() {
+				if is_visible(map, Point2::new(x, y))? {
+					*map_visible.get_mut(Point2::new(x, y)).unwrap() = 1;
+					total_visible += 1;
+				}
+			}
+		}
+		log::debug!("Map visible:\n{:?}", map_visible);
+
+		Ok(total_visible)
+	}
+
+	fn part2(map: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut max_scenic = 0;
+		for y in map.y_range() {
+			for x in map.x_range() {
+				let scenic = evaluate_scenic(map, Point2::new(x, y))?;
+
+				if scenic > max_scenic {
+					max_scenic = scenic;
+				}
+
+			}
+		}
+
+		Ok(max_scenic)
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
+}
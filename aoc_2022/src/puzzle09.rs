@@ -0,0 +1,119 @@
+use std::{collections::HashSet, fmt::Display};
+
+use anyhow::Context;
+
+use aoc_commons as aoc;
+use aoc::{anyhow, log, puzzle::{Puzzle, Registry}};
+
+use aoc::geometry::Point;
+
+type Vec2 = Point<2>;
+
+struct Rope<const N: usize> {
+	nodes: [Vec2; N]
+}
+impl<const N: usize> Rope<N> {
+	pub fn new() -> Self {
+		Self {
+			nodes: [Vec2::ZERO; N]
+		}
+	}
+
+	fn propagate(&mut self, from: usize, to: usize) -> anyhow::Result<()> {
+		let diff = self.nodes[from] - self.nodes[to];
+		let length = diff.length_chebyshev();
+		anyhow::ensure!(length <= 2, "Invalid state");
+
+		log::trace!("from: {:?}, to: {:?}, diff: {:?}, length: {}", self.nodes[from], self.nodes[to], diff, length);
+
+		if length == 2 {
+			if diff.x() == 0 || diff.y() == 0 {
+				self.nodes[to] += diff / 2;
+			} else {
+				self.nodes[to] += Vec2::new(diff.x().signum(), diff.y().signum());
+			}
+		}
+
+		log::trace!("new to: {:?}", self.nodes[to]);
+
+		Ok(())
+	}
+
+	pub fn move_head(&mut self, shift: Vec2) -> anyhow::Result<()> {
+		self.nodes[0] += shift;
+		
+		for i in 1 .. N {
+			self.propagate(i - 1, i)?;
+		}
+		
+		Ok(())
+	}
+
+	pub fn link_pos(&self, i: usize) -> Option<Vec2> {
+		if i >= N {
+			None
+		} else {
+			Some(self.nodes[i])
+		}
+	}
+
+	pub fn tail_pos(&self) -> Vec2 {
+		self.nodes[N - 1]
+	}
+}
+
+fn count_unique_positions(moves: &[(Vec2, usize)], position: impl Fn(&Rope<10>) -> Vec2) -> anyhow::Result<usize> {
+	let mut rope = Rope::<10>::new();
+
+	let mut unique_positions: HashSet<Vec2, _> = HashSet::new();
+	unique_positions.insert(position(&rope));
+
+	for &(shift, steps) in moves {
+		for _ in 0 .. steps {
+			rope.move_head(shift)?;
+			unique_positions.insert(position(&rope));
+		}
+	}
+
+	Ok(unique_positions.len())
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 9;
+
+	type Input<'a> = Vec<(Vec2, usize)>;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut moves = Vec::new();
+		for line in input.split("\n").filter(|s| !s.is_empty()) {
+			let (direction, steps_str) = line.split_once(' ').context("Failed to split line by \\n")?;
+
+			let shift = match direction {
+				"U" => Vec2::new(0, -1),
+				"D" => Vec2::new(0, 1),
+				"L" => Vec2::new(-1, 0),
+				"R" => Vec2::new(1, 0),
+				_ => anyhow::bail!("Invalid direction")
+			};
+
+			let steps: usize = steps_str.parse().context("Invalid number of steps")?;
+			moves.push((shift, steps));
+		}
+
+		Ok(moves)
+	}
+
+	fn part1(moves: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		count_unique_positions(moves, |rope| rope.link_pos(1).unwrap())
+	}
+
+	fn part2(moves: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		count_unique_positions(moves, |rope| rope.tail_pos())
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
+}
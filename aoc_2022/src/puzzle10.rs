@@ -1,9 +1,9 @@
-use std::{io::Read, fmt, ops::RangeInclusive};
+use std::{fmt::{self, Display}, ops::RangeInclusive};
 
 use anyhow::Context;
 
 use aoc_commons as aoc;
-use aoc::{anyhow, log};
+use aoc::{anyhow, log, image::{self, Image, Palette}, puzzle::{Puzzle, Registry}};
 
 #[derive(Debug)]
 enum Instruction {
@@ -11,15 +11,15 @@ enum Instruction {
 	Addx(isize)
 }
 
-struct Cpu {
-	instructions: Vec<Instruction>,
+struct Cpu<'a> {
+	instructions: &'a [Instruction],
 	registers: [isize; 1],
 	current_instruction: usize,
 	current_instruction_cycles: usize,
 	clock: usize
 }
-impl Cpu {
-	pub fn new(instructions: Vec<Instruction>) -> Self {
+impl<'a> Cpu<'a> {
+	pub fn new(instructions: &'a [Instruction]) -> Self {
 		let mut me = Self {
 			registers: [1],
 			current_instruction: 0,
@@ -96,6 +96,10 @@ impl Screen {
 
 		log::trace!("Updating pixel ({}, {}) @ {} in [{}; {}]: {}", x, y, clock, sprite.start(), sprite.end(), *pixel);
 	}
+
+	pub fn image(&self) -> Image {
+		Image::from_fn(Self::WIDTH, Self::HEIGHT, |x, y| self.rows[y * Self::WIDTH + x].color())
+	}
 }
 impl fmt::Display for Screen {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -114,24 +118,8 @@ impl fmt::Display for Screen {
 	}
 }
 
-fn main() -> anyhow::Result<()> {
-	let mut file = aoc::initialize()?;
-
-	let mut input = String::new();
-	file.read_to_string(&mut input).context("Failed to read input file")?;
-
-	let mut instructions = Vec::new();
-	for instruction_str in input.split('\n').filter(|s| !s.is_empty()) {
-		let mut parts = instruction_str.split(' ');
-		let instruction = match parts.next() {
-			Some("noop") => Instruction::Noop,
-			Some("addx") => Instruction::Addx(parts.next().context("Missing addx command value")?.parse().context("Invalid addx command value")?),
-			instr => anyhow::bail!("Invalid instruction: \"{:?}\"", instr)
-		};
-
-		instructions.push(instruction);
-	}
-
+/// Runs the program for one screen refresh, returns the signal strength sample points and the rendered screen.
+fn run(instructions: &[Instruction], frames: &str) -> anyhow::Result<(Vec<isize>, Screen)> {
 	let mut cpu = Cpu::new(instructions);
 	let mut sample_points = Vec::new();
 	let mut screen = Screen::new();
@@ -139,6 +127,7 @@ fn main() -> anyhow::Result<()> {
 	loop {
 		let reg_x = cpu.registers()[0];
 		screen.update(cpu.clock(), reg_x - 1 ..= reg_x + 1);
+		image::record_frame(frames, || screen.image())?;
 
 		cpu.tick();
 
@@ -152,8 +141,45 @@ fn main() -> anyhow::Result<()> {
 	}
 	log::debug!("Sample points: {:?}", sample_points);
 
-	println!("Sample points count: {}", sample_points.into_iter().sum::<isize>());
-	println!("Screen:\n{}", screen);
+	Ok((sample_points, screen))
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 10;
+
+	type Input<'a> = Vec<Instruction>;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut instructions = Vec::new();
+		for instruction_str in input.split('\n').filter(|s| !s.is_empty()) {
+			let mut parts = instruction_str.split(' ');
+			let instruction = match parts.next() {
+				Some("noop") => Instruction::Noop,
+				Some("addx") => Instruction::Addx(parts.next().context("Missing addx command value")?.parse().context("Invalid addx command value")?),
+				instr => anyhow::bail!("Invalid instruction: \"{:?}\"", instr)
+			};
+
+			instructions.push(instruction);
+		}
+
+		Ok(instructions)
+	}
+
+	fn part1(instructions: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let (sample_points, _) = run(instructions, "day10_part1")?;
+
+		Ok(sample_points.into_iter().sum::<isize>())
+	}
+
+	fn part2(instructions: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let (_, screen) = run(instructions, "day10_part2")?;
+
+		Ok(screen)
+	}
+}
 
-	Ok(())
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
 }
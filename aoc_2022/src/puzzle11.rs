@@ -0,0 +1,184 @@
+use std::{fmt::Display, collections::VecDeque};
+
+use anyhow::Context;
+
+use aoc_commons as aoc;
+use aoc::{anyhow, log, macros::FromStrToTryFromAdapter, numeric, puzzle::{Puzzle, Registry}};
+
+type WorryLevel = u64;
+
+#[derive(Debug, Clone)]
+enum Operand {
+	Old,
+	Value(WorryLevel)
+}
+impl<'a> TryFrom<&'a str> for Operand {
+	type Error = anyhow::Error;
+
+	fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+		match value {
+			"old" => Ok(Self::Old),
+			value => match value.parse::<WorryLevel>() {
+				Ok(v) => Ok(Self::Value(v)),
+				Err(_) => anyhow::bail!("Invalid operation target value: \"{}\"", value)
+			}
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+enum Operation {
+	Add,
+	Mul
+}
+
+#[derive(Debug, Clone)]
+struct Expression {
+	left: Operand,
+	operation: Operation,
+	right: Operand,
+}
+impl Expression {
+	fn resolve_target(target: &Operand, old_value: WorryLevel) -> WorryLevel {
+		match target {
+			Operand::Old => old_value,
+			Operand::Value(v) => *v
+		}
+	}
+
+	pub fn apply(&self, old_value: WorryLevel) -> WorryLevel {
+		let left = Self::resolve_target(&self.left, old_value);
+		let right = Self::resolve_target(&self.right, old_value);
+
+		match self.operation {
+			Operation::Add => left + right,
+			Operation::Mul => left * right
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+struct MonkeyTest {
+	divisible_by: WorryLevel,
+	monkey_true: usize,
+	monkey_false: usize
+}
+impl MonkeyTest {
+	pub fn test(&self, value: WorryLevel) -> usize {
+		if value.is_multiple_of(self.divisible_by) {
+			self.monkey_true
+		} else {
+			self.monkey_false
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+struct Monkey {
+	items: VecDeque<WorryLevel>,
+	expression: Expression,
+	test: MonkeyTest
+}
+impl Monkey {
+	pub fn new(items: Vec<WorryLevel>, expression: Expression, test: MonkeyTest) -> Self {
+		Self {
+			items: VecDeque::from(items),
+			expression,
+			test
+		}
+	}
+
+	pub fn tick(&mut self, reducer: impl Fn(WorryLevel) -> WorryLevel) -> Option<(usize, WorryLevel)> {
+		let old_level = self.items.pop_front()?;
+		let new_level = self.expression.apply(old_level);
+		let new_level = reducer(new_level);
+		log::trace!("Updated item: {} -> {}", old_level, new_level);
+		
+		let new_monkey = self.test.test(new_level);
+
+		Some((new_monkey, new_level))
+	}
+
+	pub fn receive_item(&mut self, item: WorryLevel) {
+		self.items.push_back(item);
+	}
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 11;
+
+	type Input<'a> = Vec<Monkey>;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut monkeys: Vec<Monkey> = Vec::new();
+		for monkey_str in input.split("\n\n").filter(|s| !s.is_empty()) {
+			let (_, items, left, operation, right, divisible_by, monkey_true, monkey_false) = aoc::match_tokens!(
+				monkey_str.split([' ', '\n', ':', ',']).filter(|s| !s.is_empty());
+				"Monkey", id: FromStrToTryFromAdapter<usize>,
+				"Starting", "items", ...items: Vec<FromStrToTryFromAdapter<WorryLevel>> {.0},
+				"Operation", "new", "=", left: Operand, operation: ("+" => Operation::Add, "*" => Operation::Mul), right: Operand,
+				"Test", "divisible", "by", divisible_by: FromStrToTryFromAdapter<WorryLevel> {.0},
+				"If", "true", "throw", "to", "monkey", monkey_true: FromStrToTryFromAdapter<usize> {.0},
+				"If", "false", "throw", "to", "monkey", monkey_false: FromStrToTryFromAdapter<usize> {.0}
+			).context("Failed to parse monkey input section")?;
+
+			monkeys.push(Monkey::new(
+				items,
+				Expression { left, operation, right },
+				MonkeyTest { divisible_by, monkey_true, monkey_false }
+			));
+		}
+		log::debug!("Monkeys: {:#?}", monkeys);
+
+		Ok(monkeys)
+	}
+
+	fn part1(monkeys: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut monkeys = monkeys.clone();
+
+		let mut inspected = vec![0; monkeys.len()];
+		for _round in 0 .. 20 {
+			for i in 0 .. monkeys.len() {
+				while let Some((receiver, item)) = monkeys[i].tick(|x| x / 3) {
+					inspected[i] += 1;
+					monkeys[receiver].receive_item(item);
+				}
+			}
+		}
+		let monkey_business = {
+			inspected.sort_by(|a, b| a.cmp(b).reverse());
+			
+			inspected[0] * inspected[1]
+		};
+
+		Ok(monkey_business)
+	}
+
+	fn part2(monkeys: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut monkeys2 = monkeys.clone();
+
+		let test_modulo: WorryLevel = numeric::lcm_all(monkeys2.iter().map(|m| m.test.divisible_by));
+		let mut inspected2 = vec![0usize; monkeys2.len()];
+		for _round in 0 .. 10_000 {
+			for i in 0 .. monkeys2.len() {
+				while let Some((receiver, item)) = monkeys2[i].tick(|x| x % test_modulo) {
+					inspected2[i] += 1;
+					monkeys2[receiver].receive_item(item);
+				}
+			}
+		}
+		let monkey_business2 = {
+			inspected2.sort_by(|a, b| a.cmp(b).reverse());
+			
+			inspected2[0] * inspected2[1]
+		};
+
+		Ok(monkey_business2)
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
+}
@@ -0,0 +1,75 @@
+use std::fmt::Display;
+
+use anyhow::Context;
+
+use aoc_commons as aoc;
+use aoc::{anyhow, log, geometry::{Grid2, Point}, puzzle::{Puzzle, Registry}, search};
+
+type Point2 = Point<2>;
+
+#[derive(Debug, Clone, Copy)]
+struct Height(u8);
+impl TryFrom<char> for Height {
+	type Error = anyhow::Error;
+
+	fn try_from(value: char) -> Result<Self, Self::Error> {
+		let height = match value {
+			'S' => height_value('a'),
+			'E' => height_value('z'),
+			h => height_value(h)
+		}?;
+
+		Ok(Self(height))
+	}
+}
+
+fn height_value(height: char) -> anyhow::Result<u8> {
+	anyhow::ensure!(height.is_ascii_lowercase(), "Height must be between 'a' and 'z'");
+
+	Ok(
+		(height as u32 - 'a' as u32) as u8
+	)
+}
+
+fn do_search(map: &Grid2<Height>, starts: impl IntoIterator<Item = Point2>, end: Point2) -> anyhow::Result<usize> {
+	let climb = |at: &Point2| {
+		let height = map.get(*at).unwrap().0;
+		log::trace!("Visited cell {} @ {}", at, height);
+
+		map.neighbors_orthogonal(*at).filter(move |(_, next)| height + 1 >= next.0).map(|(next, _)| next)
+	};
+
+	let search = search::bfs(starts, climb, |at| *at == end);
+	log::debug!("Path: {:?}", search.goal_path());
+
+	search.goal_cost().context("Failed to visit end")
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 12;
+
+	type Input<'a> = (Grid2<Height>, Point2, Point2);
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let (map, [start, end]) = Grid2::<Height>::parse_markers(input, ['S', 'E'])?;
+		log::debug!("Coordinates: {} -> {}", start, end);
+
+		Ok((map, start, end))
+	}
+
+	fn part1((map, start, end): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		do_search(map, [*start], *end)
+	}
+
+	fn part2((map, _, end): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let possible_starts = map.bounding_box().points_iter().filter(|p| map.get(*p).unwrap().0 == 0);
+
+		do_search(map, possible_starts, *end)
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
+}
@@ -1,9 +1,9 @@
-use std::{io::Read, fmt};
+use std::fmt::{self, Display};
 
 use anyhow::Context;
 
 use aoc_commons as aoc;
-use aoc::{anyhow, log};
+use aoc::{anyhow, log, puzzle::{Puzzle, Registry}};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 enum PacketElement {
@@ -74,7 +74,7 @@ macro_rules! scan {
 	};
 }
 
-fn parse_next<'a>(input: &'a str) -> anyhow::Result<(PacketElement, &'a str)> {
+fn parse_next(input: &str) -> anyhow::Result<(PacketElement, &str)> {
 	let mut chars = input.chars().enumerate();
 
 	let (elem, end) = match chars.next() {
@@ -87,7 +87,7 @@ fn parse_next<'a>(input: &'a str) -> anyhow::Result<(PacketElement, &'a str)> {
 			log::trace!("array inner: {}", inner);
 
 			let mut array = Vec::new();
-			while inner.len() > 0 {
+			while !inner.is_empty() {
 				let res = parse_next(inner)?;
 				array.push(res.0);
 				inner = res.1;
@@ -113,46 +113,57 @@ fn parse_next<'a>(input: &'a str) -> anyhow::Result<(PacketElement, &'a str)> {
 	Ok((elem, input.get(end ..).unwrap_or("")))
 }
 
-fn main() -> anyhow::Result<()> {
-	let mut file = aoc::initialize()?;
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 13;
 
-	let mut input = String::new();
-	file.read_to_string(&mut input).context("Failed to read input file")?;
+	type Input<'a> = Vec<[PacketElement; 2]>;
 
-	let mut pairs = Vec::new();
-	for pair in input.split("\n\n").filter(|s| !s.is_empty()) {
-		let (top_str, bottom_str) = pair.split_once('\n').context("Failed to split pair by '\\n'")?;
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut pairs = Vec::new();
+		for pair in input.split("\n\n").filter(|s| !s.is_empty()) {
+			let (top_str, bottom_str) = pair.split_once('\n').context("Failed to split pair by '\\n'")?;
 
-		let (top, _) = parse_next(top_str)?;
-		let (bottom, _) = parse_next(bottom_str)?;
+			let (top, _) = parse_next(top_str)?;
+			let (bottom, _) = parse_next(bottom_str)?;
 
-		pairs.push([top, bottom]);
+			pairs.push([top, bottom]);
+		}
+
+		Ok(pairs)
 	}
 
-	// part1
-	let mut correct_indices = Vec::new();
-	for (i, pair) in pairs.iter().enumerate() {
-		let correct_order = pair[0] <= pair[1];
-		if correct_order {
-			correct_indices.push(i + 1);
+	fn part1(pairs: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut correct_indices = Vec::new();
+		for (i, pair) in pairs.iter().enumerate() {
+			let correct_order = pair[0] <= pair[1];
+			if correct_order {
+				correct_indices.push(i + 1);
+			}
+			
+			log::debug!("{}\n<\n{}: {}", pair[0], pair[1], correct_order);
 		}
+
+		Ok(correct_indices.iter().copied().sum::<usize>())
+	}
+
+	fn part2(pairs: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut all_packets: Vec<PacketElement> = pairs.iter().flat_map(|pair| pair.iter().cloned()).collect();
+		let divider1 = PacketElement::Array(vec![PacketElement::Array(vec![PacketElement::Value(2)])]);
+		let divider2 = PacketElement::Array(vec![PacketElement::Array(vec![PacketElement::Value(6)])]);
+		all_packets.push(divider1.clone());
+		all_packets.push(divider2.clone());
+		all_packets.sort();
 		
-		log::debug!("{}\n<\n{}: {}", pair[0], pair[1], correct_order);
+		let mut all_iter = all_packets.into_iter().enumerate();
+		let divider1_index = all_iter.find(|(_, p)| p == &divider1).context("Failed to find divider 1")?.0 + 1;
+		let divider2_index = all_iter.find(|(_, p)| p == &divider2).context("Failed to find divider 2")?.0 + 1;
+
+		Ok(divider1_index * divider2_index)
 	}
-	println!("Correct indices sum: {}", correct_indices.iter().copied().sum::<usize>());
-
-	// part2
-	let mut all_packets: Vec<PacketElement> = pairs.into_iter().flat_map(|pair| pair.into_iter()).collect();
-	let divider1 = PacketElement::Array(vec![PacketElement::Array(vec![PacketElement::Value(2)])]);
-	let divider2 = PacketElement::Array(vec![PacketElement::Array(vec![PacketElement::Value(6)])]);
-	all_packets.push(divider1.clone());
-	all_packets.push(divider2.clone());
-	all_packets.sort();
-	
-	let mut all_iter = all_packets.into_iter().enumerate();
-	let divider1_index = all_iter.find(|(_, p)| p == &divider1).context("Failed to find divider 1")?.0 + 1;
-	let divider2_index = all_iter.find(|(_, p)| p == &divider2).context("Failed to find divider 2")?.0 + 1;
-	println!("Decoder key: {}", divider1_index * divider2_index);
-
-	Ok(())
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
 }
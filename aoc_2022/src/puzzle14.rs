@@ -0,0 +1,243 @@
+use std::fmt::{self, Display};
+
+use anyhow::Context;
+
+use aoc_commons as aoc;
+use aoc::{anyhow, log, puzzle::{Puzzle, Registry}};
+
+use aoc::{
+	geometry::{Grid2, Point, Rectangle, SparseGrid2},
+	image::{Palette, Rgb},
+	player,
+	macros::FromStrToTryFromAdapter,
+	polygon
+};
+
+type Point2 = Point<2>;
+type Rectangle2 = Rectangle<2>;
+
+#[derive(Clone, Copy)]
+enum Cell {
+	Source,
+	Air,
+	Rock,
+	Sand,
+	Void
+}
+impl fmt::Display for Cell {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Source => write!(f, "+"),
+			Self::Air => write!(f, "."),
+			Self::Rock => write!(f, "#"),
+			Self::Sand => write!(f, "o"),
+			Self::Void => write!(f, "~")
+		}
+	}
+}
+impl Palette for Cell {
+	fn color(&self) -> Rgb {
+		match self {
+			Self::Source => [255, 64, 64],
+			Self::Air => [24, 24, 32],
+			Self::Rock => [128, 128, 128],
+			Self::Sand => [230, 200, 120],
+			Self::Void => [0, 0, 0]
+		}
+	}
+}
+
+fn map_fill_line(map: &mut Grid2<Cell>, from: Point2, to: Point2, fill: Cell) -> anyhow::Result<()> {
+	polygon::fill_polyline(map, &[from, to], fill)
+}
+
+/// Map the sand falls through.
+trait Cave: fmt::Display {
+	/// `None` outside of the map.
+	fn cell_mut(&mut self, at: Point2) -> Option<&mut Cell>;
+	fn frame(&self, name: &str) -> anyhow::Result<()>;
+}
+impl Cave for Grid2<Cell> {
+	fn cell_mut(&mut self, at: Point2) -> Option<&mut Cell> {
+		self.get_mut(at)
+	}
+
+	fn frame(&self, name: &str) -> anyhow::Result<()> {
+		player::frame(name, self)
+	}
+}
+
+/// Cave with an endless rock floor, its map grows as far as the sand spreads.
+struct FlooredCave {
+	cells: SparseGrid2<Cell>,
+	floor: isize
+}
+impl Cave for FlooredCave {
+	fn cell_mut(&mut self, at: Point2) -> Option<&mut Cell> {
+		if at.y() == self.floor && !self.cells.contains(at) {
+			self.cells.insert(at, Cell::Rock);
+		}
+
+		Some(self.cells.get_mut(at))
+	}
+
+	fn frame(&self, name: &str) -> anyhow::Result<()> {
+		if !player::frames_enabled() {
+			return Ok(());
+		}
+
+		// only the floor cells probed by the sand are set
+		let mut grid = self.cells.to_grid();
+		for x in grid.x_range() {
+			if let Some(cell) = grid.get_mut(Point2::new(x, self.floor)) {
+				*cell = Cell::Rock;
+			}
+		}
+
+		player::frame(name, &grid)
+	}
+}
+impl fmt::Display for FlooredCave {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.cells)
+	}
+}
+
+enum TickResult {
+	Continue,
+	Rest,
+	Void
+}
+fn map_sand_tick(map: &mut impl Cave, cursor: &mut Point2) -> anyhow::Result<TickResult> {
+	macro_rules! check {
+		($x: expr, $y: expr) => {
+			{
+				let check_pos = *cursor + Point2::new($x, $y);
+				match map.cell_mut(check_pos) {
+					Some(Cell::Source | Cell::Air) => {
+						*cursor = check_pos;
+						return Ok(TickResult::Continue);
+					}
+					Some(Cell::Void) => {
+						return Ok(TickResult::Void)
+					}
+					Some(Cell::Rock | Cell::Sand) => (),
+					None => {
+						log::debug!("Map error:\n{}", map);
+						anyhow::bail!("Invalid collision check coordinates {}", check_pos)
+					}
+				}
+			}
+		};
+	}
+
+	check!(0, 1);
+	check!(-1, 1);
+	check!(1, 1);
+
+	*map.cell_mut(*cursor).context("Cursor out of bounds")? = Cell::Sand;
+
+	Ok(TickResult::Rest)
+}
+
+const SAND_SOURCE: Point2 = Point2::new(500, 0);
+
+/// Drops sand until it comes to rest at the source or falls into the void, `frames` names the recorded frame sequence.
+fn simulate(mut map: impl Cave, frames: &str) -> anyhow::Result<usize> {
+	log::debug!("Map start:\n{}", map);
+	
+	let mut cursor = SAND_SOURCE;
+	let mut sand_at_rest = 0;
+	loop {
+		match map_sand_tick(&mut map, &mut cursor)? {
+			TickResult::Continue => (),
+			TickResult::Rest => {
+				sand_at_rest += 1;
+
+				if cursor == SAND_SOURCE {
+					break
+				}
+				cursor = SAND_SOURCE;
+
+				log::trace!("Map:\n{}", map);
+				map.frame(frames)?;
+			}
+			TickResult::Void => break
+		}
+	}
+	log::debug!("Map rest:\n{}", map);
+
+	Ok(sand_at_rest)
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 14;
+
+	/// Rock lines and their bounding box.
+	type Input<'a> = (Vec<Vec<Point2>>, Rectangle2);
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut lines: Vec<Vec<Point2>> = Vec::new();
+		for line in input.split('\n').filter(|s| !s.is_empty()) {
+			let mut points = Vec::new();
+			
+			for point_str in line.split(" -> ") {
+				let (x, y) = aoc::match_tokens!(point_str.split(','); x: FromStrToTryFromAdapter<isize>, y: FromStrToTryFromAdapter<isize>)?;
+				points.push(Point2::new(x.0, y.0));
+			}
+
+			anyhow::ensure!(points.len() > 1, "Line has less than 2 points");
+			lines.push(points);
+		}
+
+		let bounding_box = lines.iter().flat_map(|p| p.iter()).fold(
+			Rectangle2 { min: Point2::new(isize::MAX, 0), max: Point2::new(0, 0) }, |acc, p| Rectangle2 {
+				min: acc.min.min(*p),
+				max: acc.max.max(*p)
+			}
+		);
+		let bounding_box = Rectangle2 {
+			min: bounding_box.min,
+			max: bounding_box.max + Point2::new(1, 1),
+		};
+
+		Ok((lines, bounding_box))
+	}
+
+	fn part1((lines, bounding_box): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let b1 = Rectangle2 {
+			min: bounding_box.min - Point2::new(1, 0),
+			max: bounding_box.max + Point2::new(1, 1)
+		};
+		log::debug!("Boundaries: {:?}", b1);
+
+		let mut map1 = Grid2::new_fill(Cell::Air, b1)?;
+		*map1.get_mut(SAND_SOURCE).unwrap() = Cell::Source;
+		for points in lines.iter() {
+			polygon::fill_polyline(&mut map1, points, Cell::Rock)?;
+		}
+		map_fill_line(&mut map1, b1.min, Point2::new(b1.min.x(), b1.max.y() - 1), Cell::Void)?;
+		map_fill_line(&mut map1, Point2::new(b1.max.x() - 1, b1.min.y()), Point2::new(b1.max.x() - 1, b1.max.y() - 1), Cell::Void)?;
+		map_fill_line(&mut map1, Point2::new(b1.min.x(), b1.max.y() - 1), Point2::new(b1.max.x() - 1, b1.max.y() - 1), Cell::Void)?;
+
+		simulate(map1, "day14_part1")
+	}
+
+	fn part2((lines, bounding_box): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut cave = FlooredCave { cells: SparseGrid2::new(Cell::Air), floor: bounding_box.max.y() + 1 };
+		cave.cells.insert(SAND_SOURCE, Cell::Source);
+		for points in lines.iter() {
+			for point in polygon::polyline_points(points) {
+				cave.cells.insert(point, Cell::Rock);
+			}
+		}
+
+		simulate(cave, "day14_part2")
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
+}
@@ -0,0 +1,146 @@
+use std::{collections::HashSet, fmt::Display};
+
+use anyhow::Context;
+
+use aoc_commons as aoc;
+use aoc::{anyhow, log, puzzle::{Parameters, Puzzle, Registry}};
+
+use aoc::{
+	geometry::{Point, Rectangle, Circle2},
+	range_set::RangeSet
+};
+
+type Point2 = Point<2>;
+type Rectangle2 = Rectangle<2>;
+
+/// Scanned row and largest coordinate of the distress beacon of the real input, the example uses `--param row=10` and
+/// `--param limit=20`.
+const SCAN_ROW: isize = 2000000;
+const AREA_LIMIT: isize = 4000000;
+const TUNING_MULTIPLIER: isize = 4000000;
+
+/// Where to look for the distress beacon.
+#[derive(Debug, Clone, Copy)]
+struct Area {
+	scan_row: isize,
+	distress_boundaries: Rectangle2
+}
+impl Area {
+	fn new(scan_row: isize, limit: isize) -> Self {
+		Self {
+			scan_row,
+			distress_boundaries: Rectangle2 { min: Point2::new(0, 0), max: Point2::new(limit + 1, limit + 1) }
+		}
+	}
+}
+
+fn cleared_count(circles: &[Circle2], beacons_and_sensors: &HashSet<Point2>, scan_row: isize) -> isize {
+	let mut cleared = RangeSet::new();
+	for range in circles.iter().filter_map(|c| c.row_range(scan_row)) {
+		cleared.insert(range);
+	}
+	for point in beacons_and_sensors.iter().filter(|p| p.y() == scan_row) {
+		cleared.remove(point.x() .. point.x() + 1);
+	}
+	log::debug!("Cleared: {}", cleared);
+	let cleared_count = cleared.len();
+	log::info!("Done part 1");
+
+	cleared_count
+}
+
+/// Points where the boundary of `circle` meets the outermost rows and columns of `area`.
+fn area_edge_crossings(circle: &Circle2, area: Rectangle2) -> impl Iterator<Item = Point2> + use<> {
+	let (min, max) = (area.min, area.max - Point2::new(1, 1));
+	let columns = [min.x(), max.x()].map(|x| (x, circle.column_range(x)));
+	let rows = [min.y(), max.y()].map(|y| (y, circle.row_range(y)));
+
+	let on_columns = columns.into_iter().filter_map(|(x, r)| r.map(|r| [Point2::new(x, r.start), Point2::new(x, r.end - 1)]));
+	let on_rows = rows.into_iter().filter_map(|(y, r)| r.map(|r| [Point2::new(r.start, y), Point2::new(r.end - 1, y)]));
+
+	on_columns.chain(on_rows).flatten()
+}
+
+fn tuning_frequency(circles: &[Circle2], distress_boundaries: Rectangle2) -> anyhow::Result<isize> {
+	// the only uncovered point is surrounded by diamonds and the edges of the area, so it lies where the boundary
+	// just outside of a diamond crosses either the boundary of another one or an edge of the area
+	let outside: Vec<Circle2> = circles.iter().map(|c| Circle2::new_radius(c.center(), c.radius() + 1)).collect();
+
+	let is_empty = |point: &Point2| distress_boundaries.contains(point) && !circles.iter().any(|c| c.contains_manhattan(*point));
+
+	let mut empty_point = None::<Point2>;
+	'toploop: for i1 in 0 .. outside.len() {
+		for i2 in i1 + 1 .. outside.len() {
+			for point in outside[i1].boundary_crossings(&outside[i2]) {
+				log::trace!("Scanning point: {}", point);
+				if is_empty(&point) {
+					empty_point = Some(point);
+					break 'toploop;
+				}
+			}
+		}
+	}
+	if empty_point.is_none() {
+		empty_point = outside.iter().flat_map(|c| area_edge_crossings(c, distress_boundaries)).find(is_empty);
+	}
+	log::info!("Done part 2");
+
+	let p = empty_point.context("Did not find empty point")?;
+	p.x().checked_mul(TUNING_MULTIPLIER).and_then(|x| x.checked_add(p.y())).context("Tuning frequency overflows")
+}
+
+aoc::record! {
+	struct SensorLine {
+		sx: isize,
+		sy: isize,
+		bx: isize,
+		by: isize
+	}
+	split [' ', '=', ',', ':'] =>
+		"Sensor", "at", "x", sx, "y", sy,
+		"closest", "beacon", "is", "at", "x", bx, "y", by
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 15;
+
+	type Input<'a> = (Vec<Circle2>, HashSet<Point2>, Area);
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		Self::parse_with(input, &Parameters::new())
+	}
+
+	fn parse_with<'a>(input: &'a str, parameters: &Parameters) -> anyhow::Result<Self::Input<'a>> {
+		let mut beacons_and_sensors = HashSet::<Point2>::new();
+		let mut circles = Vec::<Circle2>::new();
+		for SensorLine { sx, sy, bx, by } in SensorLine::parse_lines(input)? {
+			let sensor = Point2::new(sx, sy);
+			let beacon = Point2::new(bx, by);
+			beacons_and_sensors.insert(sensor);
+			beacons_and_sensors.insert(beacon);
+
+			let circle = Circle2::new(sensor, beacon);
+			log::debug!("Circle: {}", circle);
+			circles.push(circle);
+		}
+
+		let area = Area::new(parameters.get_or("row", SCAN_ROW)?, parameters.get_or("limit", AREA_LIMIT)?);
+		log::debug!("Area: {:?}", area);
+
+		Ok((circles, beacons_and_sensors, area))
+	}
+
+	fn part1((circles, beacons_and_sensors, area): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		Ok(cleared_count(circles, beacons_and_sensors, area.scan_row))
+	}
+
+	fn part2((circles, _, area): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		tuning_frequency(circles, area.distress_boundaries)
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
+}
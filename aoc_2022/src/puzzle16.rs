@@ -1,10 +1,10 @@
 use std::collections::VecDeque;
-use std::{fmt, collections::HashMap, io::Read};
+use std::{fmt::{self, Display}, collections::HashMap};
 
 use anyhow::Context;
 
 use aoc_commons as aoc;
-use aoc::{anyhow, log};
+use aoc::{anyhow, log, puzzle::{Puzzle, Registry}};
 
 use aoc::{
 	combinatorics::Combinations,
@@ -95,10 +95,8 @@ impl<const N: usize> SearchState<N> {
 
 		let node = graph.nodes.get(&pos.0).context("Invalid current position")?;
 
-		if !self.opened.contains(&pos.0) {
-			if !(0 .. i).any(|i2| self.position[i2].0 == pos.0) {
-				dest.push(Action::Open(pos.0, node.rate));
-			}
+		if !self.opened.contains(&pos.0) && !(0 .. i).any(|i2| self.position[i2].0 == pos.0) {
+			dest.push(Action::Open(pos.0, node.rate));
 		}
 
 		for edge in node.edges.iter() {
@@ -181,16 +179,16 @@ fn solve<const N: usize>(
 			continue;
 		}
 		
-		for i in 0 .. N {
-			action_pools[i].clear();
-			state.possible_actions(i, graph, &mut action_pools[i])?;
+		for (i, pool) in action_pools.iter_mut().enumerate() {
+			pool.clear();
+			state.possible_actions(i, graph, pool)?;
 		}
 
 		for combination in Combinations::new(&action_pools) {			
 			let mut new_state = state.tick();
 
-			for i in 0 .. N {
-				new_state.perform_action(i, combination[i])?;
+			for (i, action) in combination.iter().enumerate() {
+				new_state.perform_action(i, action)?;
 			}
 
 			active.push_back(new_state);
@@ -204,86 +202,93 @@ const START_VALVE: NodeName = NodeName([b'A', b'A']);
 const MAX_MINUTES: Time = 30;
 const MAX_MINUTES2: Time = 26;
 
-fn main() -> anyhow::Result<()> {
-	let mut file = aoc::initialize()?;
-
-	let mut input = String::new();
-	file.read_to_string(&mut input).context("Failed to read input file")?;
-
-	let (valve_graph, start): (Graph, NodeNameHash) = {
-		let mut assigner = BitHash64Assigner::new();
-		let mut nodes: HashMap<NodeNameHash, Node> = HashMap::new();
-		let mut rates: Vec<(NodeNameHash, Pressure)> = Vec::new();
-
-		let start = assigner.assign(START_VALVE)?;
-
-		for line in input.split('\n').filter(|s| !s.is_empty()) {
-			let (name, rate, reachable) = aoc::match_tokens!(
-				line.split([' ', '=', ',', ';']).filter(|s| !s.is_empty());
-				"Valve", name: NodeName, "has", "flow", "rate", rate: FromStrToTryFromAdapter<Pressure> {.0},
-				"tunnel" | "tunnels", "leads" | "lead", "to", "valve" | "valves", ...reachable: Vec<NodeName>
-			)?;
-
-			log::trace!("Valve {}, rate {}, reachable {:?}", name, rate, reachable);
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 16;
+
+	type Input<'a> = (Graph, NodeNameHash);
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let (valve_graph, start): (Graph, NodeNameHash) = {
+			let mut assigner = BitHash64Assigner::new();
+			let mut nodes: HashMap<NodeNameHash, Node> = HashMap::new();
+			let mut rates: Vec<(NodeNameHash, Pressure)> = Vec::new();
+
+			let start = assigner.assign(START_VALVE)?;
+
+			for (number, line) in input.split('\n').enumerate().filter(|(_, s)| !s.is_empty()) {
+				let (name, rate, reachable) = aoc::match_tokens!(
+					line.split([' ', '=', ',', ';']).filter(|s| !s.is_empty());
+					"Valve", name: NodeName, "has", "flow", "rate", rate: FromStrToTryFromAdapter<Pressure> {.0},
+					"tunnel" | "tunnels", "leads" | "lead", "to", "valve" | "valves", ...reachable: Vec<NodeName>
+				).map_err(|err| err.in_line(line).at_line_number(number + 1))?;
+
+				log::trace!("Valve {}, rate {}, reachable {:?}", name, rate, reachable);
+
+				let name = assigner.assign(name)?;
+				nodes.insert(
+					name,
+					Node {
+						rate,
+						edges: reachable.into_iter().map(
+							|r| Edge { cost: 1, end: assigner.assign(r).unwrap() }
+						).filter(|edge| edge.end != name).collect()
+					}
+				);
 
-			let name = assigner.assign(name)?;
-			nodes.insert(
-				name,
-				Node {
-					rate,
-					edges: reachable.into_iter().map(
-						|r| Edge { cost: 1, end: assigner.assign(r).unwrap() }
-					).filter(|edge| edge.end != name).collect()
+				if rate > 0 {
+					rates.push((name, rate));
 				}
-			);
-
-			if rate > 0 {
-				rates.push((name, rate));
 			}
-		}
-		rates.sort_by(|a, b| a.1.cmp(&b.1).reverse());
+			rates.sort_by(|a, b| a.1.cmp(&b.1).reverse());
 
-		{
-			let collapsible_nodes: Vec<NodeNameHash> = nodes.iter().filter(|(_, node)| node.rate == 0).map(|(name, _)| *name).collect();
-			for name in collapsible_nodes {
-				if name == start {
-					continue;
-				}
+			{
+				let collapsible_nodes: Vec<NodeNameHash> = nodes.iter().filter(|(_, node)| node.rate == 0).map(|(name, _)| *name).collect();
+				for name in collapsible_nodes {
+					if name == start {
+						continue;
+					}
 
-				let node = nodes.remove(&name).unwrap();
+					let node = nodes.remove(&name).unwrap();
 
-				for (&other_name, other_node) in nodes.iter_mut() {
-					if let Some((edge_i, _)) = other_node.edges.iter().enumerate().find(|(_, e)| e.end == name) {
-						let cost = other_node.edges.swap_remove(edge_i).cost;
+					for (&other_name, other_node) in nodes.iter_mut() {
+						if let Some((edge_i, _)) = other_node.edges.iter().enumerate().find(|(_, e)| e.end == name) {
+							let cost = other_node.edges.swap_remove(edge_i).cost;
 
-						for edge in node.edges.iter().filter(|e| e.end != other_name) {
-							log::trace!(
-								"Collapsing edge from {} through {} to {}, cost {}",
-								assigner.original(other_name).unwrap(),
-								assigner.original(name).unwrap(),
-								assigner.original(edge.end).unwrap(),
-								cost + edge.cost
-							);
+							for edge in node.edges.iter().filter(|e| e.end != other_name) {
+								log::trace!(
+									"Collapsing edge from {} through {} to {}, cost {}",
+									assigner.original(other_name).unwrap(),
+									assigner.original(name).unwrap(),
+									assigner.original(edge.end).unwrap(),
+									cost + edge.cost
+								);
 
-							other_node.edges.push(Edge { cost: cost + edge.cost, end: edge.end });
+								other_node.edges.push(Edge { cost: cost + edge.cost, end: edge.end });
+							}
 						}
 					}
 				}
 			}
-		}
 
-		(Graph { nodes, sorted_rates: rates, assigner }, start)
-	};
-	
-	log::trace!("Valve graph: {:#?}", valve_graph);
+			(Graph { nodes, sorted_rates: rates, assigner }, start)
+		};
 
-	// part1
-	let best_accumulated = solve::<1>(&valve_graph, start, MAX_MINUTES)?;
-	println!("Best accumulated: {}", best_accumulated);
+		log::trace!("Valve graph: {:#?}", valve_graph);
+
+		Ok((valve_graph, start))
+	}
 
-	// part2
-	let best_accumulated2 = solve::<2>(&valve_graph, start, MAX_MINUTES2)?;
-	println!("Best accumulated2: {}", best_accumulated2);
+	fn part1((valve_graph, start): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		solve::<1>(valve_graph, *start, MAX_MINUTES)
+	}
+
+	fn part2((valve_graph, start): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		solve::<2>(valve_graph, *start, MAX_MINUTES2)
+	}
+}
 
-	Ok(())
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
 }
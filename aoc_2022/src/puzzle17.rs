@@ -1,60 +1,28 @@
-use std::{fmt, io::Read};
+use std::{cell::OnceCell, fmt::{self, Display}};
 
 use anyhow::Context;
 
 use aoc_commons as aoc;
-use aoc::{anyhow, log};
+use aoc::{anyhow, log, cycle::{self, CycleDetector}, puzzle::{Puzzle, Registry}, range_set::RangeSet};
 
 use aoc::geometry::Point;
 
 type Point2 = Point<2>;
 
-#[derive(Debug)]
-struct Intervals {
-	intervals: Vec<[isize; 2]>
-}
-impl Intervals {
-	pub fn new() -> Self {
-		Self { intervals: vec![[0, 1]] }
-	}
-
-	pub fn add(&mut self, interval: [isize; 2]) {
-		for i in 0 .. self.intervals.len() {
-			if interval[0] <= self.intervals[i][1] + 1 && interval[1] >= self.intervals[i][0] {
-				self.intervals[i][0] = self.intervals[i][0].min(interval[0]);
-				self.intervals[i][1] = self.intervals[i][1].max(interval[1]);
-
-				return;
-			}
-		}
-		
-		self.intervals.push(interval);
-	}
-
-	pub fn contains(&self, v: isize) -> bool {
-		for interval in self.intervals.iter().rev() {
-			if v >= interval[0] && v < interval[1] {
-				return true;
-			}
-		}
-
-		false
-	}
-
-	pub fn lowest(&self) -> isize {
-		self.intervals.iter().map(|[l, _]| *l).min().unwrap()
-	}
-}
-
+/// Occupied cells of each column, the floor is at 0 and the tower grows towards negative y.
 #[derive(Debug)]
 struct Heights {
-	columns: [Intervals; Self::WIDTH]
+	columns: [RangeSet<isize>; Self::WIDTH]
 }
 impl Heights {
 	const WIDTH: usize = 7;
 
 	pub fn new() -> Self {
-		Self { columns: [(); Self::WIDTH].map(|_| Intervals::new()) }
+		Self { columns: [(); Self::WIDTH].map(|_| RangeSet::from(0 .. 1)) }
+	}
+
+	fn column_top(column: &RangeSet<isize>) -> isize {
+		column.bounds().unwrap().start
 	}
 
 	pub fn shift(&mut self, rock: &mut RockFormation, shift: RockShift) -> bool {
@@ -69,14 +37,14 @@ impl Heights {
 				break;
 			}
 
-			if self.columns[point.x() as usize].contains(point.y()) {
+			if self.columns[point.x() as usize].contains(&point.y()) {
 				to_move = false;
 				break;
 			}
 		}
 		
 		if to_move {
-			rock.position = rock.position + shift_point;
+			rock.position += shift_point;
 			log::trace!("Shifted: {} (shift: {})", rock, shift_point);
 
 			false
@@ -90,9 +58,9 @@ impl Heights {
 					Some([l, h]) => Some([(*l).min(point.y()), (*h).max(point.y() + 1)])
 				};
 			}
-			for i in 0 .. Self::WIDTH {
-				if let Some(interval) = intervals[i] {
-					self.columns[i].add(interval);
+			for (column, interval) in self.columns.iter_mut().zip(intervals) {
+				if let Some([l, h]) = interval {
+					column.insert(l .. h);
 				}
 			}
 			log::trace!("At rest: {}", rock);
@@ -107,22 +75,13 @@ impl Heights {
 	fn height_profile(&self) -> [isize; Self::WIDTH] {
 		let mut result = [0isize; Self::WIDTH];
 
-		for i in 0 .. Self::WIDTH {
-			result[i] = self.columns[i].lowest();
+		for (height, column) in result.iter_mut().zip(self.columns.iter()) {
+			*height = Self::column_top(column);
 		}
 
 		result
 	}
 
-	fn all_same(&self) -> Option<isize> {
-		let profile = self.height_profile();
-		if profile.iter().all(|&h| h == profile[0]) {
-			Some(profile[0])
-		} else {
-			None
-		}
-	}
-
 	pub fn lowest(&self) -> isize {
 		self.height_profile().into_iter().min().unwrap()
 	}
@@ -131,7 +90,7 @@ impl fmt::Display for Heights {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		let mut heights = [0; Self::WIDTH];
 		for (i, height) in self.columns.iter().enumerate() {
-			heights[i] = height.lowest();
+			heights[i] = Self::column_top(height);
 		}
 		
 		write!(f, "{:?}", heights)
@@ -165,7 +124,7 @@ impl TryFrom<char> for RockShift {
 	}
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Shape {
 	Hline,
 	Plus,
@@ -217,7 +176,7 @@ impl Shape {
 			Self::Square => Self::SHAPE_SQUARE
 		};
 
-		points.into_iter().copied()
+		points.iter().copied()
 	}
 
 	pub fn height(&self) -> isize {
@@ -242,93 +201,113 @@ impl fmt::Display for RockFormation {
 	}
 }
 
-fn simulate(
-	streams: &[RockShift],
-	rounds: usize,
-	mut jet_i: usize,
-	mut current_shape: Shape,
-	periods: &mut Vec<(usize, Shape, usize, isize)>
-) -> anyhow::Result<usize> {
-	let mut heights = Heights::new();
-	log::debug!("Heights: {}", heights);
-
-	for round in 0 .. rounds {
-		if let Some(height) = heights.all_same() {
-			if round > 0 {
-				periods.push((jet_i, current_shape, round, height));
-			}
-		}
+type Fingerprint = (usize, Shape, [isize; Heights::WIDTH]);
+
+struct Chamber<'a> {
+	streams: &'a [RockShift],
+	heights: Heights,
+	jet_i: usize,
+	shape: Shape
+}
+impl<'a> Chamber<'a> {
+	pub fn new(streams: &'a [RockShift]) -> Self {
+		Self { streams, heights: Heights::new(), jet_i: 0, shape: Shape::Hline }
+	}
 
+	pub fn drop_rock(&mut self) {
 		let mut current = RockFormation {
-			shape: current_shape,
-			position: Point2::new(2, heights.lowest() - 3 - current_shape.height())
+			shape: self.shape,
+			position: Point2::new(2, self.heights.lowest() - 3 - self.shape.height())
 		};
-		current_shape = current_shape.next();
+		self.shape = self.shape.next();
 		log::debug!("Spawned: {}", current);
 
 		loop {
-			let stream = streams[jet_i];
-			jet_i = (jet_i + 1) % streams.len();
-			heights.shift(&mut current, stream);
+			let stream = self.streams[self.jet_i];
+			self.jet_i = (self.jet_i + 1) % self.streams.len();
+			self.heights.shift(&mut current, stream);
 
-			if heights.shift(&mut current, RockShift::Down) {
+			if self.heights.shift(&mut current, RockShift::Down) {
 				break;
 			}
 		}
 	}
 
-	log::info!("total intervals: {}", heights.columns.iter().map(|i| i.intervals.len()).sum::<usize>());
+	pub fn tower_height(&self) -> i64 {
+		-self.heights.lowest() as i64
+	}
+
+	/// Next jet, next shape and the surface relative to its lowest point.
+	pub fn fingerprint(&self) -> Fingerprint {
+		let profile = self.heights.height_profile();
+		let highest = profile.into_iter().min().unwrap();
 
-	Ok(heights.lowest().abs() as usize)
+		(self.jet_i, self.shape, profile.map(|h| h - highest))
+	}
 }
 
 const MAX_ROUNDS: usize = 2022;
 const MAX_ROUNDS2: usize = 1000000000000;
+const MAX_CYCLE_SEARCH: usize = 100000;
+
+/// Drops rocks until the tower heights repeat.
+fn tower_cycle(streams: &[RockShift]) -> anyhow::Result<CycleDetector<Fingerprint>> {
+	let mut chamber = Chamber::new(streams);
+	let detector = cycle::detect(
+		&mut chamber,
+		MAX_CYCLE_SEARCH,
+		|chamber| { chamber.drop_rock(); Ok(()) },
+		|chamber| (chamber.fingerprint(), chamber.tower_height())
+	)?;
+	log::info!("Cycle: {:?}", detector.cycle());
+	log::info!("total intervals: {}", chamber.heights.columns.iter().map(|c| c.ranges().len()).sum::<usize>());
 
-fn main() -> anyhow::Result<()> {
-	let mut file = aoc::initialize()?;
+	Ok(detector)
+}
 
-	let mut input = String::new();
-	file.read_to_string(&mut input).context("Failed to read input file")?;
+/// Jet pattern and the tower cycle found with it.
+///
+/// Both parts extrapolate from the same cycle, so it is only simulated by the first part which needs it.
+struct Jets {
+	streams: Vec<RockShift>,
+	cycle: OnceCell<CycleDetector<Fingerprint>>
+}
+impl Jets {
+	fn tower_cycle(&self) -> anyhow::Result<&CycleDetector<Fingerprint>> {
+		if let Some(cycle) = self.cycle.get() {
+			return Ok(cycle);
+		}
 
-	let mut streams = Vec::<RockShift>::new();
-	for ch in input.chars().filter(|c| !c.is_whitespace()) {
-		streams.push(RockShift::try_from(ch).context("Invalid input")?);
+		let cycle = tower_cycle(&self.streams)?;
+		Ok(self.cycle.get_or_init(|| cycle))
 	}
+}
 
-	let mut periods = Vec::new();
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 17;
 
-	let part1 = simulate(
-		&streams,
-		MAX_ROUNDS,
-		0,
-		Shape::Hline,
-		&mut periods
-	)?;
-	if periods.len() < 2 {
-		anyhow::bail!("Not enough periods :(");
+	type Input<'a> = Jets;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut streams = Vec::<RockShift>::new();
+		for ch in input.chars().filter(|c| !c.is_whitespace()) {
+			streams.push(RockShift::try_from(ch).context("Invalid input")?);
+		}
+
+		Ok(Jets { streams, cycle: OnceCell::new() })
 	}
-	log::info!("Periods: {:?}", periods);
-	
-	let start_round = periods[0].2;
-	let start_height = periods[0].3;
-	let period_rounds = periods[1].2 - start_round;
-	let period_height = periods[1].3 - start_height;
-	let skipped_periods = (MAX_ROUNDS2 - start_round) / period_rounds;
-	let skipped_rounds = skipped_periods * period_rounds;
-
-	let part2 = simulate(
-		&streams,
-		MAX_ROUNDS2 - skipped_rounds - start_round,
-		periods[0].0,
-		periods[0].1,
-		&mut periods
-	)?;
 
-	println!("Tower height: {}", part1);
-	println!("Tower height 2: {}", (-start_height as usize) + (-period_height as usize) * skipped_periods + part2);
+	fn part1(jets: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		jets.tower_cycle()?.extrapolate(MAX_ROUNDS)
+	}
 
-	log::info!("Done");
+	fn part2(jets: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		jets.tower_cycle()?.extrapolate(MAX_ROUNDS2)
+	}
+}
 
-	Ok(())
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
 }
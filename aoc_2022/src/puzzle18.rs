@@ -0,0 +1,118 @@
+use std::fmt::Display;
+
+use anyhow::Context;
+
+use aoc_commons as aoc;
+use aoc::{anyhow, log, puzzle::{Puzzle, Registry}, search};
+
+use aoc::geometry::{Point, Rectangle, Grid3};
+
+type Point3 = Point<3>;
+type Rectangle3 = Rectangle<3>;
+
+/// Flood fills the air around `start`, returns whether it reaches outside of the grid and the lava area it touches.
+fn count_reachable(
+	start: Point3,
+	lava: &Grid3<bool>,
+	seen: &mut Grid3<bool>
+) -> (bool, usize) {
+	let is_lava = |point: Point3| lava.get(point).copied().unwrap_or(false);
+	if is_lava(start) || seen.get(start).copied().unwrap_or(true) {
+		return (false, 0);
+	}
+
+	// points outside of the grid are visited, but not expanded
+	let search = search::bfs(
+		[start],
+		|current: &Point3| {
+			let inside = lava.get(*current).is_some();
+			current.neighbors_orthogonal().filter(move |side| inside && !is_lava(*side))
+		},
+		|_| false
+	);
+
+	let mut reachable = false;
+	let mut area = 0;
+	for (point, _) in search.visited() {
+		match seen.get_mut(*point) {
+			None => reachable = true,
+			Some(seen) => {
+				*seen = true;
+				area += point.neighbors_orthogonal().filter(|side| is_lava(*side)).count();
+			}
+		}
+	}
+
+	(reachable, area)
+}
+
+/// Total surface area and the area reachable from outside.
+fn surface_areas(lava: &Grid3<bool>) -> anyhow::Result<(usize, usize)> {
+	let mut surface_area = 0;
+	let mut reachable_area = 0;
+
+	let mut seen = Grid3::new_fill(false, lava.bounding_box())?;
+	for point in lava.bounding_box().points_iter() {
+		log::trace!("Point: {} (is lava: {})", point, lava.get(point).unwrap());
+
+		let (reachable, area) = count_reachable(point, lava, &mut seen);
+		surface_area += area;
+		if reachable {
+			reachable_area += area;
+		}
+	}
+
+	Ok((surface_area, reachable_area))
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 18;
+
+	type Input<'a> = Grid3<bool>;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut points = Vec::new();
+		let mut bounding_box = Rectangle3 { min: Point3::MAX, max: Point3::MIN };
+
+		for (number, line) in input.lines().enumerate().filter(|(_, s)| !s.is_empty()) {
+			let (x, y, z) = aoc::match_tokens!(
+				line.split(',');
+				x: aoc::macros::FromStrToTryFromAdapter<isize>,
+				y: aoc::macros::FromStrToTryFromAdapter<isize>,
+				z: aoc::macros::FromStrToTryFromAdapter<isize>
+			).map_err(|err| err.in_line(line).at_line_number(number + 1))?;
+
+			let current = Point3::new(x.0, y.0, z.0);
+			points.push(current);
+			bounding_box.min = bounding_box.min.min(current);
+			bounding_box.max = bounding_box.max.max(current);
+		}
+
+		let bounding_box = Rectangle3 {
+			min: bounding_box.min - Point3::new(1, 1, 1),
+			max: bounding_box.max + Point3::new(2, 2, 2)
+		};
+		log::debug!("Total bb: {}", bounding_box);
+
+		let mut lava = Grid3::new_fill(false, bounding_box)?;
+		for point in points {
+			*lava.get_mut(point).context("Lava out of bounds")? = true;
+		}
+
+		Ok(lava)
+	}
+
+	fn part1(lava: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		Ok(surface_areas(lava)?.0)
+	}
+
+	fn part2(lava: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		Ok(surface_areas(lava)?.1)
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
+}
@@ -0,0 +1,283 @@
+use std::{
+	fmt::{self, Display},
+	ops::Index
+};
+
+use aoc_commons as aoc;
+use aoc::{anyhow, log, puzzle::{Puzzle, Registry}};
+
+use aoc::geometry::Point;
+
+type Time = usize;
+
+#[derive(Debug, Clone, Copy)]
+#[repr(usize)]
+enum Kind {
+	Ore = 0,
+	Clay = 1,
+	Obsidian = 2,
+	Geode = 3
+}
+
+type Minerals = Point<4, usize>;
+impl Index<Kind> for Minerals {
+	type Output = usize;
+
+	fn index(&self, index: Kind) -> &Self::Output {
+		&self.data()[index as usize]
+	}
+}
+
+/// Time needed to produce `minerals` at `rate` per minute, for each kind separately.
+fn div_by_rate(minerals: Minerals, rate: Minerals) -> Option<Minerals> {
+	let mut result = [0; 4];
+	for (v, (&a, &b)) in result.iter_mut().zip(minerals.data().iter().zip(rate.data())) {
+		if a != 0 {
+			*v = (a + b - 1).checked_div(b)?;
+		}
+	}
+
+	Some(Minerals::from(result))
+}
+
+#[derive(Clone)]
+struct RobotBlueprint {
+	time: Time,
+	cost: Minerals,
+	production: Minerals
+}
+impl fmt::Display for RobotBlueprint {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "({} in {} -> {})", self.cost, self.time, self.production)
+	}
+}
+
+#[derive(Clone)]
+struct Blueprint {
+	id: usize,
+	robots: [RobotBlueprint; 4]
+}
+impl Index<Kind> for Blueprint {
+	type Output = RobotBlueprint;
+
+	fn index(&self, index: Kind) -> &Self::Output {
+		&self.robots[index as usize]
+	}
+}
+impl fmt::Display for Blueprint {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f, "Blueprint {}:\n  {:?}{}\n  {:?}{}\n  {:?}{}\n  {:?}{}",
+			self.id,
+			Kind::Ore, self.robots[Kind::Ore as usize],
+			Kind::Clay, self.robots[Kind::Clay as usize],
+			Kind::Obsidian, self.robots[Kind::Obsidian as usize],
+			Kind::Geode, self.robots[Kind::Geode as usize]
+		)
+	}
+}
+
+struct Factory {
+	blueprint: Blueprint,
+	max_time: Time,
+	best: SearchValue,
+	total_explored: usize
+}
+struct SearchValue {
+	time: Time,
+	minerals: Minerals,
+	robots: Minerals
+}
+impl SearchValue {
+	pub fn until(&self, target: Minerals) -> Option<Self> {
+		div_by_rate(target.saturating_sub(self.minerals), self.robots).map(
+			|required| {
+				let elapsed = required[Kind::Ore].max(required[Kind::Clay]).max(required[Kind::Obsidian]).max(required[Kind::Geode]);
+
+				Self {
+					time: self.time + elapsed,
+					minerals: self.minerals + self.robots * elapsed,
+					robots: self.robots
+				}
+			}
+		)
+	}
+}
+impl fmt::Debug for SearchValue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {		
+		write!(f, "{}+{}@{}", self.minerals, self.robots, self.time)
+	}
+}
+impl Factory {
+	pub fn new(blueprint: Blueprint, max_time: Time) -> Self {
+		Self {
+			blueprint,
+			max_time,
+			best: SearchValue { time: 0, minerals: Minerals::ZERO, robots: Minerals::ZERO },
+			total_explored: 0
+		}
+	}
+
+	pub fn simulate(&mut self, start_minerals: Minerals, start_robots: Minerals) -> usize {
+		let start = SearchValue { time: 0, minerals: start_minerals, robots: start_robots };
+		self.explore(start);
+		
+		self.best.minerals[Kind::Geode]
+	}
+
+	fn until_build(&self, start: &SearchValue, kind: Kind) -> Option<SearchValue> {
+		let blueprint = &self.blueprint[kind];
+		match start.until(blueprint.cost) {
+			None => None,
+			Some(mut until) => {
+				until.time += blueprint.time;
+				until.minerals = until.minerals.checked_sub(blueprint.cost).unwrap() + until.robots * blueprint.time;
+				until.robots += blueprint.production;
+
+				Some(until)
+			}
+		}
+	}
+
+	fn definitely_better(&self, left: &SearchValue, right: &SearchValue) -> bool {
+		let result = if right.time >= left.time && left.minerals[Kind::Geode] > right.minerals[Kind::Geode] {
+			true
+		} else {
+			let left_projection = left.minerals[Kind::Geode] + left.robots[Kind::Geode] * (self.max_time - left.time);
+
+			let x = right.minerals.checked_sub(self.blueprint[Kind::Geode].cost).map(|_| 1).unwrap_or(2);
+			let right_projection = right.minerals[Kind::Geode] + right.robots[Kind::Geode] * (self.max_time - right.time) + (self.max_time - right.time).saturating_sub(x).pow(2);
+
+			left_projection > right_projection
+		};
+
+		log::trace!("{:?} vs {:?} = {}", left, right, result);
+		result
+	}
+
+	fn explore(&mut self, start: SearchValue) {
+		if self.total_explored.is_multiple_of(10000000) {
+			log::debug!("explore({:?})#{}", start, self.total_explored);
+		} else {
+			log::trace!("explore({:?})#{}", start, self.total_explored);
+		}
+		self.total_explored += 1;
+		
+		if start.time > self.max_time {
+			log::trace!("Over time");
+			return;
+		}
+
+		if self.definitely_better(&self.best, &start) {
+			log::trace!("Culled");
+			return;
+		}
+
+		if let Some(until) = self.until_build(&start, Kind::Geode) {
+			self.explore(until);
+		}
+		if let Some(until) = self.until_build(&start, Kind::Obsidian) {
+			self.explore(until);
+		}
+		if let Some(until) = self.until_build(&start, Kind::Clay) {
+			self.explore(until);
+		}
+		if let Some(until) = self.until_build(&start, Kind::Ore) {
+			self.explore(until);
+		}
+
+		let until_end = self.max_time - start.time;
+		let until_end = SearchValue {
+			time: self.max_time,
+			minerals: start.minerals + start.robots * until_end,
+			robots: start.robots
+		};
+		if until_end.minerals[Kind::Geode] > self.best.minerals[Kind::Geode] {
+			log::debug!("New best: {:?}", until_end);
+			self.best = until_end;
+		}
+	}
+}
+
+aoc::record! {
+	struct BlueprintLine {
+		id: usize,
+		ore_ore: usize,
+		clay_ore: usize,
+		obsidian_ore: usize,
+		obsidian_clay: usize,
+		geode_ore: usize,
+		geode_obsidian: usize
+	}
+	split [' ', ':', '.'] =>
+		"Blueprint", id,
+		"Each", "ore", "robot", "costs", ore_ore, "ore",
+		"Each", "clay", "robot", "costs", clay_ore, "ore",
+		"Each", "obsidian", "robot", "costs", obsidian_ore, "ore", "and", obsidian_clay, "clay",
+		"Each", "geode", "robot", "costs", geode_ore, "ore", "and", geode_obsidian, "obsidian"
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 19;
+
+	type Input<'a> = Vec<Blueprint>;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut blueprints = Vec::<Blueprint>::new();
+		for line in BlueprintLine::parse_lines(input)? {
+			let BlueprintLine { id, ore_ore, clay_ore, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian } = line;
+
+			blueprints.push(
+				Blueprint {
+					id,
+					robots: [
+						RobotBlueprint { time: 1, cost: Minerals::from([ore_ore, 0, 0, 0]), production: Minerals::from([1, 0, 0, 0]) },
+						RobotBlueprint { time: 1, cost: Minerals::from([clay_ore, 0, 0, 0]), production: Minerals::from([0, 1, 0, 0]) },
+						RobotBlueprint { time: 1, cost: Minerals::from([obsidian_ore, obsidian_clay, 0, 0]), production: Minerals::from([0, 0, 1, 0]) },
+						RobotBlueprint { time: 1, cost: Minerals::from([geode_ore, 0, geode_obsidian, 0]), production: Minerals::from([0, 0, 0, 1]) }
+					]
+				}
+			);
+		}
+
+		Ok(blueprints)
+	}
+
+	fn part1(blueprints: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut quality_score = 0;
+		for blueprint in blueprints.clone().into_iter() {
+			let blueprint_id = blueprint.id;
+			
+			let mut factory = Factory::new(blueprint, 24);
+			let geodes = factory.simulate(Minerals::ZERO, Minerals::from([1, 0, 0, 0]));
+
+			log::info!("Blueprint: {} = {}", blueprint_id, geodes);
+
+			quality_score += blueprint_id * geodes;
+		}
+
+		Ok(quality_score)
+	}
+
+	fn part2(blueprints: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut geodes_product = 1;
+		for blueprint in blueprints.iter().take(3).cloned() {
+			let blueprint_id = blueprint.id;
+			
+			let mut factory = Factory::new(blueprint, 32);
+			let geodes = factory.simulate(Minerals::ZERO, Minerals::from([1, 0, 0, 0]));
+
+			log::info!("Blueprint: {} = {}", blueprint_id, geodes);
+
+			geodes_product *= geodes;
+		}
+
+		Ok(geodes_product)
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
+}
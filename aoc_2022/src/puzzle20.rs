@@ -1,12 +1,11 @@
 use std::{
-	fmt,
-	io::Read
+	fmt::{self, Display}
 };
 
 use anyhow::Context;
 
 use aoc_commons as aoc;
-use aoc::{anyhow, log};
+use aoc::{anyhow, log, puzzle::{Puzzle, Registry}};
 
 struct Element {
 	previous: usize,
@@ -90,7 +89,7 @@ impl List {
 		let mod_shift = shift % (self.items.len() as isize - 1);
 		log::trace!("Shifting from {} by {} ({})", original_index, shift, mod_shift);
 
-		for _ in 0 .. mod_shift.abs() as usize {
+		for _ in 0 .. mod_shift.unsigned_abs() {
 			self.swap(original_index, mod_shift.is_positive());
 		}
 	}
@@ -127,7 +126,7 @@ impl List {
 const SUM_INDICES: [usize; 3] = [1000, 2000, 3000];
 
 fn solve(numbers: &[isize], mix_count: usize) -> isize {
-	let mut list = List::new(&numbers);
+	let mut list = List::new(numbers);
 
 	for _ in 0 .. mix_count {
 		for i in 0 .. list.len() {
@@ -151,26 +150,35 @@ fn solve(numbers: &[isize], mix_count: usize) -> isize {
 
 const DECRYPTION_KEY: isize = 811589153;
 
-fn main() -> anyhow::Result<()> {
-	let mut file = aoc::initialize()?;
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 20;
 
-	let mut input = String::new();
-	file.read_to_string(&mut input).context("Failed to read input file")?;
+	type Input<'a> = Vec<isize>;
 
-	let mut numbers = Vec::<isize>::new();
-	for line in input.lines().filter(|s| !s.is_empty()) {
-		let number: isize = line.parse().context("Failed to parse line as a number")?;
-		numbers.push(number);
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut numbers = Vec::<isize>::new();
+		for line in input.lines().filter(|s| !s.is_empty()) {
+			let number: isize = line.parse().context("Failed to parse line as a number")?;
+			numbers.push(number);
+		}
+		log::trace!("Numbers: {:?}", numbers);
+
+		Ok(numbers)
 	}
-	log::trace!("Numbers: {:?}", numbers);
 
-	// part1
-	println!("Part 1: {}", solve(&numbers, 1));
-	
-	numbers.iter_mut().for_each(|n| *n *= DECRYPTION_KEY);
-	println!("Part 2: {}", solve(&numbers, 10));
-	
-	log::info!("Done");
+	fn part1(numbers: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		Ok(solve(numbers, 1))
+	}
+
+	fn part2(numbers: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let numbers: Vec<isize> = numbers.iter().map(|n| n * DECRYPTION_KEY).collect();
+
+		Ok(solve(&numbers, 10))
+	}
+}
 
-	Ok(())
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
 }
@@ -1,18 +1,18 @@
 use std::{
-	io::Read, collections::HashMap
+	collections::HashMap, fmt::Display, str::FromStr
 };
 
 use anyhow::Context;
 
 use aoc_commons as aoc;
-use aoc::{anyhow, log};
+use aoc::{anyhow, puzzle::{Puzzle, Registry}};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 struct MonkeyName([u8; 4]);
-impl<'a> TryFrom<&'a str> for MonkeyName {
-	type Error = anyhow::Error;
+impl FromStr for MonkeyName {
+	type Err = anyhow::Error;
 
-	fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
 		let value = value.as_bytes();
 		anyhow::ensure!(value.len() == 4, "Monkey name must have exactly 4 bytes");
 
@@ -30,23 +30,12 @@ enum Operation {
 	Div,
 	Eq
 }
-impl<'a> TryFrom<&'a str> for Operation {
-	type Error = anyhow::Error;
-
-	fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-		match value {
-			"+" => Ok(Self::Add),
-			"-" => Ok(Self::Sub),
-			"*" => Ok(Self::Mul),
-			"/" => Ok(Self::Div),
-			_ => anyhow::bail!("Invalid Operation: \"{}\"", value)
-		}
-	}
-}
 
+#[derive(Clone)]
 enum Monkey {
 	Value(isize),
-	Variable(MonkeyName),
+	/// The unknown value of the human.
+	Variable,
 	Compute {
 		left: MonkeyName,
 		op: Operation,
@@ -55,9 +44,9 @@ enum Monkey {
 }
 
 fn evaluate_monkey(monkeys: &HashMap<MonkeyName, Monkey>, start: &MonkeyName) -> Option<isize> {
-	match monkeys.get(&start).unwrap() {
+	match monkeys.get(start).unwrap() {
 		Monkey::Value(value) => Some(*value),
-		Monkey::Variable(_) => None,
+		Monkey::Variable => None,
 		Monkey::Compute { left, op, right } => {			
 			let left = evaluate_monkey(monkeys, left);
 			let right = evaluate_monkey(monkeys, right);
@@ -81,7 +70,7 @@ fn evaluate_monkey(monkeys: &HashMap<MonkeyName, Monkey>, start: &MonkeyName) ->
 
 fn evaluate_reverse(monkeys: &HashMap<MonkeyName, Monkey>, start: &MonkeyName, target: isize) -> isize {
 	match monkeys.get(start).unwrap() {
-		Monkey::Variable(_) => target,
+		Monkey::Variable => target,
 		Monkey::Compute { left, op, right } => {
 			let left_value = evaluate_monkey(monkeys, left);
 			let right_value = evaluate_monkey(monkeys, right);
@@ -118,43 +107,67 @@ fn evaluate_reverse(monkeys: &HashMap<MonkeyName, Monkey>, start: &MonkeyName, t
 const ROOT_MONKEY: MonkeyName = MonkeyName([b'r', b'o', b'o', b't']);
 const HUMAN_MONKEY: MonkeyName = MonkeyName([b'h', b'u', b'm', b'n']);
 
-fn main() -> anyhow::Result<()> {
-	let mut file = aoc::initialize()?;
+aoc::record! {
+	struct ComputeLine {
+		name: MonkeyName,
+		left: MonkeyName,
+		op: Operation,
+		right: MonkeyName
+	}
+	split [' ', ':'] => name, left, op: ("+" => Operation::Add, "-" => Operation::Sub, "*" => Operation::Mul, "/" => Operation::Div), right
+}
 
-	let mut input = String::new();
-	file.read_to_string(&mut input).context("Failed to read input file")?;
+aoc::record! {
+	struct ValueLine {
+		name: MonkeyName,
+		value: isize
+	}
+	split [' ', ':'] => name, value
+}
 
-	let mut monkeys: HashMap<MonkeyName, Monkey> = HashMap::new();
-	for line in input.lines().filter(|s| !s.is_empty()) {
-		if let Ok((name, left, op, right)) = aoc::match_tokens!(
-			line.split([' ', ':']).filter(|s| !s.is_empty()); name: MonkeyName, left: MonkeyName, op: Operation, right: MonkeyName
-		) {
-			monkeys.insert(name, Monkey::Compute { left, op, right });
-			continue;
-		}
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 21;
 
-		if let Ok((name, value)) = aoc::match_tokens!(
-			line.split([' ', ':']).filter(|s| !s.is_empty()); name: MonkeyName, value: aoc::macros::FromStrToTryFromAdapter<isize> {.0}
-		) {
-			monkeys.insert(name, Monkey::Value(value));
-			continue;
+	type Input<'a> = HashMap<MonkeyName, Monkey>;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut monkeys: HashMap<MonkeyName, Monkey> = HashMap::new();
+		for line in input.lines().filter(|s| !s.is_empty()) {
+			if let Ok(ComputeLine { name, left, op, right }) = ComputeLine::try_from(line) {
+				monkeys.insert(name, Monkey::Compute { left, op, right });
+				continue;
+			}
+
+			if let Ok(ValueLine { name, value }) = ValueLine::try_from(line) {
+				monkeys.insert(name, Monkey::Value(value));
+				continue;
+			}
+
+			anyhow::bail!("Invalid input line \"{}\"", line);
 		}
 
-		anyhow::bail!("Invalid input line \"{}\"", line);
+		Ok(monkeys)
 	}
-	
-	let root_value = evaluate_monkey(&monkeys, &ROOT_MONKEY).unwrap();
 
-	*monkeys.get_mut(&HUMAN_MONKEY).unwrap() = Monkey::Variable(HUMAN_MONKEY);
-	match monkeys.get_mut(&ROOT_MONKEY).unwrap() {
-		Monkey::Compute { op, .. } => { *op = Operation::Eq; }
-		_ => unimplemented!()
+	fn part1(monkeys: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		evaluate_monkey(monkeys, &ROOT_MONKEY).context("Root value depends on a variable")
 	}
-	let human_value = evaluate_reverse(&monkeys, &ROOT_MONKEY, 0);
-	
-	println!("Root value: {}", root_value);
-	println!("Human value: {}", human_value);
-	log::info!("Done");
 
-	Ok(())
+	fn part2(monkeys: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut monkeys = monkeys.clone();
+
+		*monkeys.get_mut(&HUMAN_MONKEY).context("Missing human")? = Monkey::Variable;
+		match monkeys.get_mut(&ROOT_MONKEY).context("Missing root")? {
+			Monkey::Compute { op, .. } => { *op = Operation::Eq; }
+			_ => anyhow::bail!("Root must compute a value")
+		}
+
+		Ok(evaluate_reverse(&monkeys, &ROOT_MONKEY, 0))
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
 }
@@ -1,7 +1,4 @@
-use std::{
-	io::Read,
-	fmt
-};
+use std::fmt::{self, Display};
 
 use anyhow::Context;
 
@@ -9,7 +6,8 @@ use aoc_commons as aoc;
 use aoc::{
 	anyhow,
 	log,
-	geometry::{Point, Rectangle, Grid2}
+	geometry::{Point, Rectangle, Grid2},
+	puzzle::{Puzzle, Registry}
 };
 
 type Point2 = Point<2>;
@@ -22,6 +20,20 @@ enum Cell {
 	Rock,
 	Cursor(Facing)
 }
+impl TryFrom<char> for Cell {
+	type Error = anyhow::Error;
+
+	fn try_from(value: char) -> Result<Self, Self::Error> {
+		let me = match value {
+			' ' => Self::Void,
+			'.' => Self::Ground,
+			'#' => Self::Rock,
+			_ => anyhow::bail!("Invalid Cell char")
+		};
+
+		Ok(me)
+	}
+}
 impl fmt::Display for Cell {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match self {
@@ -65,15 +77,29 @@ enum Facing {
 }
 type Transform = Facing;
 impl Facing {
-	pub fn direction(&self) -> Point2 {
+	/// Number of clockwise quarter turns from [`Facing::Up`].
+	fn turns(self) -> isize {
 		match self {
-			Self::Up => Point2::new(0, -1),
-			Self::Right => Point2::new(1, 0),
-			Self::Down => Point2::new(0, 1),
-			Self::Left => Point2::new(-1, 0)
+			Self::Up => 0,
+			Self::Right => 1,
+			Self::Down => 2,
+			Self::Left => 3
 		}
 	}
 
+	fn from_turns(turns: isize) -> Self {
+		match turns.rem_euclid(4) {
+			0 => Self::Up,
+			1 => Self::Right,
+			2 => Self::Down,
+			_ => Self::Left
+		}
+	}
+
+	pub fn direction(&self) -> Point2 {
+		Point2::new(0, -1).rot90(self.turns())
+	}
+
 	pub fn score(&self) -> usize {
 		match self {
 			Self::Up => 3,
@@ -84,24 +110,11 @@ impl Facing {
 	}
 
 	pub fn rotate(self, rhs: Self) -> Self {
-		match (self, rhs) {
-			(a, Self::Up) | (Self::Up, a) => a,
-			(Self::Down, Self::Right) | (Self::Right, Self::Down) => Self::Left,
-			(Self::Left, Self::Right) | (Self::Right, Self::Left) => Self::Up,
-			(Self::Left, Self::Down) | (Self::Down, Self::Left) => Self::Right,
-			(Self::Right, Self::Right) => Self::Down,
-			(Self::Down, Self::Down) => Self::Up,
-			(Self::Left, Self::Left) => Self::Down
-		}
+		Self::from_turns(self.turns() + rhs.turns())
 	}
 
 	pub fn inverse(self) -> Self {
-		match self {
-			Self::Up => Self::Up,
-			Self::Down => Self::Down,
-			Self::Left => Self::Right,
-			Self::Right => Self::Left
-		}
+		Self::from_turns(-self.turns())
 	}
 }
 impl fmt::Display for Facing {
@@ -158,8 +171,8 @@ fn simulate_2d(grid: &mut Grid2<Cell>, commands: &[Command], start: Point2) -> a
 			Command::RotateRight => { facing = facing.rotate(Facing::Right); },
 			Command::Move(steps) => {
 				let boundaries = match facing {
-					Facing::Left | Facing::Right => row_non_void(&grid, position.y()),
-					Facing::Up | Facing::Down => column_non_void(&grid, position.x())
+					Facing::Left | Facing::Right => row_non_void(grid, position.y()),
+					Facing::Up | Facing::Down => column_non_void(grid, position.x())
 				}?;
 				let direction = facing.direction();
 
@@ -279,13 +292,9 @@ fn modulo_point(point: Point2, size: isize) -> Point2 {
 }
 
 fn rotate_point(point: Point2, transform: Transform, size: isize) -> Point2 {
-	let s = size - 1;
-	let p = match transform {
-		Transform::Up => point,
-		Transform::Left => Point2::new(point.y(), s - point.x()),
-		Transform::Down => Point2::new(s - point.x(), s - point.y()),
-		Transform::Right => Point2::new(s - point.y(), point.x())
-	};
+	// rotate around the center of the side, doubled so that the center has integer coordinates
+	let s = Point2::new(size - 1, size - 1);
+	let p = ((point * 2 - s).rot90(transform.turns()) + s) / 2;
 
 	modulo_point(p, size)
 }
@@ -363,161 +372,133 @@ fn simulate_cube(grid: &mut Grid2<Cell>, commands: &[Command], cube: &Cube, star
 	Ok((position, facing))
 }
 
-fn main() -> anyhow::Result<()> {
-	let mut file = aoc::initialize()?;
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 22;
 
-	let mut input = String::new();
-	file.read_to_string(&mut input).context("Failed to read input file")?;
+	type Input<'a> = (Grid2<Cell>, Vec<Command>, Point2);
 
-	let mut grid = {
-		let mut width = 0;
-		let mut height = 0;
-		for (i, line) in input.lines().enumerate() {
-			if line.is_empty() {
-				break;
-			}
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let grid = Grid2::parse_padded(input, Cell::Void)?;
+		log::debug!("Grid bounds: {}", grid.bounding_box());
+		log::trace!("Map:\n{}", grid);
 
-			width = width.max(line.len());
-			height = i + 1;
-		}
+		let commands = {
+			let input_line = input.lines().nth(grid.bounding_box().size().y() as usize + 1).context("Invalid input")?;
 
-		let mut grid = Grid2::<Cell>::new_fill(
-			Cell::Void,
-			Rectangle2 {
-				min: Point2::new(0, 0),
-				max: Point2::new(width as isize, height as isize)
-			}
-		)?;
-		log::debug!("Grid bounds: {}", grid.bounding_box());
+			let mut commands = Vec::<Command>::new();
 
-		for (y, line) in input.lines().take(grid.bounding_box().size().y() as usize).enumerate() {
-			for (x, ch) in line.chars().enumerate() {
-				match ch {
-					'.' => { *grid.get_mut(Point2::new(x as isize, y as isize)).unwrap() = Cell::Ground; }
-					'#' => { *grid.get_mut(Point2::new(x as isize, y as isize)).unwrap() = Cell::Rock; }
-					_ => ()
+			let mut start_index = 0;
+			for (index, ch) in input_line.char_indices() {
+				if !ch.is_numeric() {
+					commands.push(
+						Command::try_from(&input_line[start_index .. index])?
+					);
+					commands.push(
+						Command::try_from(&input_line[index .. index + 1])?
+					);
+
+					start_index = index + 1;
 				}
 			}
-		}
-
-		grid
-	};
-	log::trace!("Map:\n{}", grid);
+			if start_index != input_line.len() {
+				commands.push(
+					Command::try_from(&input_line[start_index ..])?
+				);
+			}
 
-	let commands = {
-		let input_line = input.lines().skip(grid.bounding_box().size().y() as usize + 1).next().context("Invalid input")?;
+			commands
+		};
+		log::trace!("Commands: {:?}", commands);
 
-		let mut commands = Vec::<Command>::new();
+		let start = row_non_void(&grid, 0)?.min;
 
-		let mut start_index = 0;
-		for (index, ch) in input_line.char_indices() {
-			if !ch.is_numeric() {
-				commands.push(
-					Command::try_from(&input_line[start_index .. index])?
-				);
-				commands.push(
-					Command::try_from(&input_line[index .. index + 1])?
-				);
+		Ok((grid, commands, start))
+	}
 
-				start_index = index + 1;
-			}
-		}
-		if start_index != input_line.len() {
-			commands.push(
-				Command::try_from(&input_line[start_index ..])?
-			);
-		}
+	fn part1((grid, commands, start): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let (position, facing) = simulate_2d(&mut grid.clone(), commands, *start)?;
 
-		commands
-	};
-	log::trace!("Commands: {:?}", commands);
+		Ok((position.x() + 1) * 4 + (position.y() + 1) * 1000 + facing.score() as isize)
+	}
 
-	let cube_size = grid.bounding_box().size().x().max(grid.bounding_box().size().y()) / 4;
-	let start = row_non_void(&grid, 0)?.min;
-	// let start = Point2::new(1 * cube_size, 1 * cube_size);
-	let cube = {
-		anyhow::ensure!(grid.bounding_box().size().x().min(grid.bounding_box().size().y()) / 3 == cube_size, "Cube size sanity check failed");
+	fn part2((grid, commands, start): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let start = *start;
+		let cube_size = grid.bounding_box().size().x().max(grid.bounding_box().size().y()) / 4;
+		// let start = Point2::new(1 * cube_size, 1 * cube_size);
+		let cube = {
+			anyhow::ensure!(grid.bounding_box().size().x().min(grid.bounding_box().size().y()) / 3 == cube_size, "Cube size sanity check failed");
 		
-		let side_offset = Point2::new(cube_size, cube_size);
-		let cube_side = Rectangle2 { min: Point2::new(0, 0), max: side_offset };
-		log::debug!("Cube side: {}", cube_side);
-
-		let top = CubeSide {
-			kind: CubeSideKind::Top,
-			bounding_box: cube_side + start,
-			transform: Transform::Up
-		};
-
-		macro_rules! find_side {
-			(
-				$kind: ident:
-				$(
-					($offset_x: literal, $offset_y: literal) $transform: ident
-				),+
-			) => {
-				None
-				$(
-					.or_else(|| {
-						let bounding_box = top.bounding_box + Point2::new($offset_x, $offset_y) * cube_size;
-						match grid.get(bounding_box.min) {
-							Some(Cell::Ground | Cell::Rock) => Some(CubeSide {
-								kind: CubeSideKind::$kind,
-								bounding_box,
-								transform: Transform::$transform
-							}),
-							_ => None
-						}
-					})
-				)+
+			let side_offset = Point2::new(cube_size, cube_size);
+			let cube_side = Rectangle2 { min: Point2::new(0, 0), max: side_offset };
+			log::debug!("Cube side: {}", cube_side);
+
+			let top = CubeSide {
+				kind: CubeSideKind::Top,
+				bounding_box: cube_side + start,
+				transform: Transform::Up
 			};
-		}
-
-		Cube {
-			left: find_side!(
-				Left:
-				(-1, 1) Left,
-				(-1, 2) Down
-			).unwrap(),
-			right: find_side!(
-				Right:
-				(1, 0) Up,
-				(1, 2) Down
-			).unwrap(),
-			far: find_side!(
-				Far:
-				(-2, 1) Down,
-				(-1, 3) Right
-			).unwrap(),
-			near: find_side!(
-				Near:
-				(0, 1) Up
-			).unwrap(),
-			bottom: find_side!(
-				Bottom:
-				(0, 2) Up
-			).unwrap(),
-			top
-		}
-	};
-	log::debug!("Cube:\n{}", cube);
-	
-	let part1 = {
-		let (position, facing) = simulate_2d(&mut grid, &commands, start)?;
 
-		(position.x() + 1) * 4 + (position.y() + 1) * 1000 + facing.score() as isize
-	};
-	log::info!("part1: {}", part1);
+			macro_rules! find_side {
+				(
+					$kind: ident:
+					$(
+						($offset_x: literal, $offset_y: literal) $transform: ident
+					),+
+				) => {
+					None
+					$(
+						.or_else(|| {
+							let bounding_box = top.bounding_box + Point2::new($offset_x, $offset_y) * cube_size;
+							match grid.get(bounding_box.min) {
+								Some(Cell::Ground | Cell::Rock) => Some(CubeSide {
+									kind: CubeSideKind::$kind,
+									bounding_box,
+									transform: Transform::$transform
+								}),
+								_ => None
+							}
+						})
+					)+
+				};
+			}
 
-	let part2 = {
-		let (position, facing) = simulate_cube(&mut grid, &commands, &cube, (start, CubeSideKind::Top))?;
+			Cube {
+				left: find_side!(
+					Left:
+					(-1, 1) Left,
+					(-1, 2) Down
+				).unwrap(),
+				right: find_side!(
+					Right:
+					(1, 0) Up,
+					(1, 2) Down
+				).unwrap(),
+				far: find_side!(
+					Far:
+					(-2, 1) Down,
+					(-1, 3) Right
+				).unwrap(),
+				near: find_side!(
+					Near:
+					(0, 1) Up
+				).unwrap(),
+				bottom: find_side!(
+					Bottom:
+					(0, 2) Up
+				).unwrap(),
+				top
+			}
+		};
+		log::debug!("Cube:\n{}", cube);
 
-		(position.x() + 1) * 4 + (position.y() + 1) * 1000 + facing.score() as isize
-	};
-	log::info!("part2: {}", part2);
+		let (position, facing) = simulate_cube(&mut grid.clone(), commands, &cube, (start, CubeSideKind::Top))?;
 
-	println!("Final password 2: {}", part2);
-	println!("Final password: {}", part1);
-	log::info!("Done");
+		Ok((position.x() + 1) * 4 + (position.y() + 1) * 1000 + facing.score() as isize)
+	}
+}
 
-	Ok(())
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
 }
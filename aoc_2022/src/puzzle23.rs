@@ -1,15 +1,13 @@
-use std::{
-	io::Read,
-	fmt
-};
-
-use anyhow::Context;
+use std::fmt::{self, Display};
 
 use aoc_commons as aoc;
 use aoc::{
 	anyhow,
 	log,
-	geometry::{Point, Rectangle, Grid2}
+	geometry::{Point, Rectangle, Grid2},
+	image::{Palette, Rgb},
+	player,
+	puzzle::{Puzzle, Registry}
 };
 
 type Point2 = Point<2>;
@@ -22,6 +20,19 @@ enum Cell {
 	Considered,
 	Blocked
 }
+impl TryFrom<char> for Cell {
+	type Error = anyhow::Error;
+
+	fn try_from(value: char) -> Result<Self, Self::Error> {
+		let me = match value {
+			'.' => Self::Empty,
+			'#' => Self::Elf,
+			_ => anyhow::bail!("Invalid Cell char")
+		};
+
+		Ok(me)
+	}
+}
 impl fmt::Display for Cell {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match self {
@@ -32,6 +43,16 @@ impl fmt::Display for Cell {
 		}
 	}
 }
+impl Palette for Cell {
+	fn color(&self) -> Rgb {
+		match self {
+			Self::Empty => [24, 24, 32],
+			Self::Elf => [64, 200, 64],
+			Self::Considered => [220, 200, 64],
+			Self::Blocked => [200, 64, 64]
+		}
+	}
+}
 
 #[derive(Debug, Clone, Copy)]
 enum Direction {
@@ -69,6 +90,7 @@ impl Direction {
 	}
 }
 
+#[derive(Clone)]
 struct Elf {
 	position: Point2,
 	start_direction: Direction,
@@ -80,20 +102,10 @@ impl Elf {
 	}
 
 	pub fn step_begin(&mut self, grid: &mut Grid2<Cell>) {
-		const AROUND: [Point2; 8] = [
-			Point2::new(1, 0),
-			Point2::new(0, 1),
-			Point2::new(-1, 0),
-			Point2::new(0, -1),
-			Point2::new(1, 1),
-			Point2::new(1, -1),
-			Point2::new(-1, 1),
-			Point2::new(-1, -1)
-		];
 		let queue = self.start_direction.next4();
 		self.start_direction = queue[1];
 
-		if !AROUND.into_iter().any(|p| matches!(grid.get(self.position + p).unwrap(), Cell::Elf)) {
+		if !grid.neighbors_all(self.position).any(|(_, c)| matches!(c, Cell::Elf)) {
 			return;
 		}
 
@@ -101,7 +113,7 @@ impl Elf {
 			let points = d.directions();
 
 			let any_blocked = points.into_iter().any(
-				|p| matches!(grid.get(self.position + p).unwrap(), Cell::Elf)
+				|p| matches!(grid.get(self.position + p), Some(Cell::Elf))
 			);
 
 			if !any_blocked {
@@ -146,93 +158,59 @@ impl Elf {
 	}
 }
 
-fn main() -> anyhow::Result<()> {
-	let mut file = aoc::initialize()?;
-
-	let mut input = String::new();
-	file.read_to_string(&mut input).context("Failed to read input file")?;
-
-	let (mut grid, mut elves) = {
-		let mut width = 0;
-		let mut height = 0;
-		for (i, line) in input.lines().enumerate() {
-			if line.is_empty() {
-				break;
-			}
-
-			width = width.max(line.len());
-			height = i + 1;
-		}
-
-		let mut grid = Grid2::<Cell>::new_fill(
-			Cell::Empty,
-			Rectangle2 {
-				min: Point2::new(-(width as isize), -(height as isize)),
-				max: Point2::new((width * 2) as isize, (height * 2) as isize)
-			}
-		)?;
-		log::debug!("Grid bounds: {}", grid.bounding_box());
-
-		let mut elves = Vec::new();
-		for (y, line) in input.lines().filter(|s| !s.is_empty()).enumerate() {
-			for (x, ch) in line.chars().enumerate() {
-				match ch {
-					'.' => (),
-					'#' => {
-						let elf = Elf::nex(Point2::new(x as isize, y as isize));
-						*grid.get_mut(elf.position).unwrap() = Cell::Elf;
-						elves.push(elf);
-					}
-					_ => anyhow::bail!("Invalid input")
-				}
-			}
-		}
-
-		(grid, elves)
-	};
-	log::trace!("Start map:\n{}", grid);
-
-	let mut grid1 = grid.clone();
+/// Runs rounds until no elf moves or `max_rounds` rounds are done, returns the number of rounds.
+fn simulate(grid: &mut Grid2<Cell>, elves: &mut [Elf], max_rounds: usize, frames: &str) -> anyhow::Result<usize> {
 	let mut round = 0;
-	loop {
+	while round < max_rounds {
 		round += 1;
 		log::debug!("Round: {}", round);
 
 		for elf in elves.iter_mut() {
-			elf.step_begin(&mut grid);
+			elf.step_begin(grid);
 		}
 		log::trace!("Map:\n{}", grid);
 		let mut any_moved = false;
 		for elf in elves.iter_mut() {
-			any_moved |= elf.step_end(&mut grid);
+			any_moved |= elf.step_end(grid);
 		}
 		log::trace!("Map:\n{}", grid);
-
-		if round == 10 {
-			grid1 = grid.clone();
-			log::info!("part1");
-		}
+		player::frame(frames, grid)?;
 
 		if !any_moved {
 			break;
 		}
 	}
 
-	let part1 = {
-		let mut min_bb = Rectangle2 {
-			min: Point2::MAX,
-			max: Point2::MIN
-		};
-		for y in grid1.y_range() {
-			for x in grid1.x_range() {
-				let point = Point2::new(x, y);
-				if let Cell::Elf = grid1.get(point).unwrap() {
-					min_bb.min = min_bb.min.min(point);
-					min_bb.max = min_bb.max.max(point);
-				}
-			}
-		}
-		min_bb.max = min_bb.max + Point2::new(1, 1);
+	Ok(round)
+}
+
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 23;
+
+	type Input<'a> = (Grid2<Cell>, Vec<Elf>);
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut grid = Grid2::<Cell>::parse(input)?;
+		// elves spread out, cells outside of the map are only ever needed by get_mut
+		grid.set_growing(Some(|| Cell::Empty));
+
+		let elves: Vec<Elf> = grid.bounding_box().points_iter().filter(
+			|p| matches!(grid.get(*p), Some(Cell::Elf))
+		).map(Elf::nex).collect();
+		log::trace!("Start map:\n{}", grid);
+
+		Ok((grid, elves))
+	}
+
+	fn part1((grid, elves): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut grid1 = grid.clone();
+		simulate(&mut grid1, &mut elves.clone(), 10, "day23_part1")?;
+
+		let min_bb = Rectangle2::from_points(
+			grid1.bounding_box().points_iter().filter(|p| matches!(grid1.get(*p), Some(Cell::Elf)))
+		);
 		log::debug!("Minimum bb: {}", min_bb);
 		
 		let mut empty_tiles = 0;
@@ -242,12 +220,14 @@ fn main() -> anyhow::Result<()> {
 			}
 		}
 
-		empty_tiles
-	};
+		Ok(empty_tiles)
+	}
 
-	println!("Empty tiles: {}", part1);
-	println!("Settled after: {}", round);
-	log::info!("Done");
+	fn part2((grid, elves): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		simulate(&mut grid.clone(), &mut elves.clone(), usize::MAX, "day23_part2")
+	}
+}
 
-	Ok(())
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
 }
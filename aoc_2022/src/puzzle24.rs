@@ -1,6 +1,5 @@
 use std::{
-	io::Read,
-	fmt, collections::VecDeque
+	fmt::{self, Display}, collections::VecDeque
 };
 
 use anyhow::Context;
@@ -9,7 +8,10 @@ use aoc_commons as aoc;
 use aoc::{
 	anyhow,
 	log,
-	geometry::{Point, Rectangle, Grid2}
+	geometry::{Point, Rectangle, Grid2},
+	image::{Palette, Rgb},
+	player,
+	puzzle::{Puzzle, Registry}
 };
 
 type Point2 = Point<2>;
@@ -25,10 +27,7 @@ impl Cell {
 	pub const EMPTY: Self = Self::Ground([None, None, None, None]);
 
 	pub fn reachable(&self) -> bool {
-		match self {
-			Self::Ground([None, None, None, None]) => true,
-			_ => false
-		}
+		matches!(self, Self::Ground([None, None, None, None]))
 	}
 
 	pub fn blizzard_count(&self) -> usize {
@@ -84,6 +83,20 @@ impl fmt::Display for Cell {
 	}
 }
 
+impl Palette for Cell {
+	fn color(&self) -> Rgb {
+		match self {
+			Self::Wall => [128, 128, 128],
+			Self::Expedition => [255, 140, 32],
+			Self::Ground(_) => {
+				// brighter with more blizzards in the cell
+				let count = self.blizzard_count() as u8;
+				[24 + count * 40, 24 + count * 50, 32 + count * 55]
+			}
+		}
+	}
+}
+
 #[derive(Debug, Clone, Copy)]
 enum Direction {
 	Up,
@@ -137,16 +150,16 @@ impl fmt::Display for Direction {
 }
 
 fn next_wrap(grid: &Grid2<Cell>, position: Point2, direction: Direction) -> Point2 {
-	let check_position = position + direction.direction();
+	let bounding_box = grid.bounding_box();
+	let inside_walls = Rectangle2 {
+		min: bounding_box.min + Point2::new(1, 1),
+		max: bounding_box.max - Point2::new(1, 1)
+	};
 
-	match grid.get(check_position).unwrap() {
-		Cell::Wall => grid.bounding_box().wrap_around(
-			check_position + direction.direction()
-		) + direction.direction(),
-		_ => check_position
-	}
+	inside_walls.step_wrapping(position, direction.direction())
 }
 
+#[derive(Clone)]
 struct Blizzard {
 	position: Point2,
 	direction: Direction
@@ -186,6 +199,9 @@ fn update_blizzards(grid: &mut Grid2<Cell>, blizzards: &mut [Blizzard]) {
 	}
 }
 
+/// Expeditions of the current minute and the next one.
+///
+/// The blizzards move between minutes, so each minute is searched on its own instead of using [`aoc::search::bfs`].
 struct SwapQueue<T> {
 	from: VecDeque<T>,
 	to: VecDeque<T>
@@ -219,7 +235,7 @@ impl<T> SwapQueue<T> {
 	}
 }
 
-fn find_path(grid: &mut Grid2<Cell>, blizzards: &mut [Blizzard], start: Point2, end: Point2) -> anyhow::Result<usize> {
+fn find_path(grid: &mut Grid2<Cell>, blizzards: &mut [Blizzard], start: Point2, end: Point2, frames: &str) -> anyhow::Result<usize> {
 	let mut round = 0;
 
 	let mut search_queue = SwapQueue::<Point2>::new();
@@ -251,8 +267,7 @@ fn find_path(grid: &mut Grid2<Cell>, blizzards: &mut [Blizzard], start: Point2,
 				_ => ()
 			}
 
-			for direction in [Direction::Up, Direction::Down, Direction::Left, Direction::Right] {
-				let point = expedition + direction.direction();
+			for point in expedition.neighbors_orthogonal() {
 				if grid.get(point).map(|c| c.reachable()).unwrap_or(false) {
 					search_queue.push(point);
 					*grid.get_mut(point).context("Invalid reachable position")? = Cell::Expedition;
@@ -262,78 +277,64 @@ fn find_path(grid: &mut Grid2<Cell>, blizzards: &mut [Blizzard], start: Point2,
 		search_queue.swap();
 
 		log::trace!("Map:\n{}", grid);
+		player::frame(frames, grid)?;
 	}
 
 	Ok(round - 1)
 }
 
-fn main() -> anyhow::Result<()> {
-	let mut file = aoc::initialize()?;
-
-	let mut input = String::new();
-	file.read_to_string(&mut input).context("Failed to read input file")?;
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 24;
 
-	let (mut grid, mut blizzards, start, end) = {
-		let mut width = 0;
-		let mut height = 0;
-		for (i, line) in input.lines().enumerate() {
-			if line.is_empty() {
-				break;
-			}
-
-			width = width.max(line.len());
-			height = i + 1;
-		}
+	type Input<'a> = (Grid2<Cell>, Vec<Blizzard>, Point2, Point2);
 
-		let mut grid = Grid2::<Cell>::new_fill(
-			Cell::EMPTY,
-			Rectangle2 {
-				min: Point2::new(0, 0),
-				max: Point2::new(width as isize, height as isize)
-			}
-		)?;
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut grid = Grid2::<Cell>::parse(input)?;
 		log::debug!("Grid bounds: {}", grid.bounding_box());
-		let mut blizzards = Vec::new();
 
-		for (y, line) in input.lines().filter(|s| !s.is_empty()).enumerate() {
-			for (x, ch) in line.chars().enumerate() {
-				let point = Point2::new(x as isize, y as isize);
-				let cell = Cell::try_from(ch)?;
-				
-				match cell {
-					Cell::Ground(
-						[Some(d), None, None, None]
-						| [None, Some(d), None, None]
-						| [None, None, Some(d), None]
-						| [None, None, None, Some(d)]
-					) => {
-						blizzards.push(Blizzard { position: point, direction: d });
-					}
-					_ => ()
-				}
-
-				*grid.get_mut(point).unwrap() = cell;
+		let mut blizzards = Vec::new();
+		for point in grid.bounding_box().points_iter() {
+			if let Cell::Ground(
+				[Some(d), None, None, None]
+				| [None, Some(d), None, None]
+				| [None, None, Some(d), None]
+				| [None, None, None, Some(d)]
+			) = grid.get(point).unwrap() {
+				blizzards.push(Blizzard { position: point, direction: *d });
 			}
 		}
 
 		let start = Point2::new(1, 0);
 		let end = Point2::new(grid.bounding_box().size().x() - 2, grid.bounding_box().size().y() - 1);
-		*grid.get_mut(start).unwrap() = Cell::Expedition;
+		*grid.get_mut(start).context("Invalid start position")? = Cell::Expedition;
+		log::trace!("Start map:\n{}", grid);
 
-		(grid, blizzards, start, end)
-	};
-	log::trace!("Start map:\n{}", grid);
-
-	let to_end = find_path(&mut grid, &mut blizzards, start, end)?;
-	log::info!("to_end: {}", to_end);
-	let to_beginning = find_path(&mut grid, &mut blizzards, end, start)?;
-	log::info!("to_beginning: {}", to_beginning);
-	let to_end_again = find_path(&mut grid, &mut blizzards, start, end)?;
-	log::info!("to_end_again: {}", to_end_again);
-	
-	println!("To end: {}", to_end);
-	println!("Another one: {}", to_end + to_beginning + to_end_again);
-	log::info!("Done");
-
-	Ok(())
+		Ok((grid, blizzards, start, end))
+	}
+
+	fn part1((grid, blizzards, start, end): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let to_end = find_path(&mut grid.clone(), &mut blizzards.clone(), *start, *end, "day24_part1")?;
+		log::info!("to_end: {}", to_end);
+
+		Ok(to_end)
+	}
+
+	fn part2((grid, blizzards, start, end): &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let (mut grid, mut blizzards) = (grid.clone(), blizzards.clone());
+
+		let to_end = find_path(&mut grid, &mut blizzards, *start, *end, "day24_part2")?;
+		log::info!("to_end: {}", to_end);
+		let to_beginning = find_path(&mut grid, &mut blizzards, *end, *start, "day24_part2")?;
+		log::info!("to_beginning: {}", to_beginning);
+		let to_end_again = find_path(&mut grid, &mut blizzards, *start, *end, "day24_part2")?;
+		log::info!("to_end_again: {}", to_end_again);
+
+		Ok(to_end + to_beginning + to_end_again)
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
 }
@@ -1,14 +1,10 @@
-use std::{
-	io::Read,
-	fmt
-};
-
-use anyhow::Context;
+use std::fmt::{self, Display};
 
 use aoc_commons as aoc;
 use aoc::{
 	anyhow,
-	log
+	log,
+	puzzle::{Puzzle, Registry}
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,7 +15,7 @@ enum SnafuDigit {
 	MinusOne,
 	MinusTwo
 }
-impl<'a> TryFrom<char> for SnafuDigit {
+impl TryFrom<char> for SnafuDigit {
 	type Error = anyhow::Error;
 
 	fn try_from(value: char) -> Result<Self, Self::Error> {
@@ -94,6 +90,7 @@ impl SnafuNumber {
 		Self(vec![SnafuDigit::Zero])
 	}
 
+	#[cfg(test)]
 	pub fn one() -> Self {
 		Self(vec![SnafuDigit::One])
 	}
@@ -168,29 +165,43 @@ impl fmt::Display for SnafuNumber {
 	}
 }
 
-fn main() -> anyhow::Result<()> {
-	let mut file = aoc::initialize()?;
+struct Solution;
+impl Puzzle for Solution {
+	const YEAR: u16 = 2022;
+	const DAY: u8 = 25;
 
-	let mut input = String::new();
-	file.read_to_string(&mut input).context("Failed to read input file")?;
+	type Input<'a> = Vec<SnafuNumber>;
 
-	let mut numbers = Vec::new();
-	for line in input.lines().filter(|s| !s.is_empty()) {
-		numbers.push(
-			SnafuNumber::try_from(line)?
-		);
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+		let mut numbers = Vec::new();
+		for line in input.lines().filter(|s| !s.is_empty()) {
+			numbers.push(
+				SnafuNumber::try_from(line)?
+			);
+		}
+
+		Ok(numbers)
 	}
 
-	let mut sum = SnafuNumber::zero();
-	for number in numbers.iter() {
-		log::debug!("Number: {} = {}", number, number.to_decimal());
-		sum.add(number);
+	fn part1(numbers: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		let mut sum = SnafuNumber::zero();
+		for number in numbers.iter() {
+			log::debug!("Number: {} = {}", number, number.to_decimal());
+			sum.add(number);
+		}
+		log::info!("Sum: {} = {}", sum, sum.to_decimal());
+
+		Ok(sum)
 	}
-	log::info!("Sum: {} = {}", sum, sum.to_decimal());
-	
-	log::info!("Done");
 
-	Ok(())
+	/// The last day only has one puzzle.
+	fn part2(_numbers: &Self::Input<'_>) -> anyhow::Result<impl Display> {
+		Ok("none")
+	}
+}
+
+pub fn register(registry: &mut Registry) {
+	registry.register::<Solution>();
 }
 
 #[cfg(test)]
@@ -0,0 +1,24 @@
+use std::path::Path;
+
+use aoc_commons as aoc;
+use aoc::puzzle::{check_expected, parse_expected};
+
+fn check(slow: bool) {
+	let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/examples");
+	let table = std::fs::read_to_string(dir.join("answers.txt")).unwrap();
+	let expected = parse_expected(&table).unwrap();
+
+	let mismatches = check_expected(&aoc2022::registry(), 2022, &dir, &expected, slow);
+	assert!(mismatches.is_empty(), "{} mismatches:\n{}", mismatches.len(), mismatches.join("\n"));
+}
+
+#[test]
+fn test_examples() {
+	check(false);
+}
+
+#[test]
+#[ignore = "slow in debug builds"]
+fn test_examples_slow() {
+	check(true);
+}
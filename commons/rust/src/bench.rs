@@ -0,0 +1,219 @@
+use std::{
+	fmt,
+	fs::OpenOptions,
+	io::Write,
+	path::{Path, PathBuf},
+	time::{Duration, Instant}
+};
+
+use anyhow::Context;
+
+/// Header of benchmark CSV files, same columns as hyperfine exports.
+pub const CSV_HEADER: &str = "command,mean,stddev,median,user,system,min,max";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BenchConfig {
+	/// Print the time of each phase after solving.
+	pub time: bool,
+	/// Number of repeated runs to compute statistics from, no benchmark when `0`.
+	pub runs: usize,
+	/// CSV file to which statistics of each phase are appended.
+	pub csv: Option<PathBuf>
+}
+
+/// Wall clock and CPU time of a single run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sample {
+	pub wall: Duration,
+	pub user: Duration,
+	pub system: Duration
+}
+
+/// User and system CPU time of this process so far, zero where not available.
+#[cfg(unix)]
+fn cpu_times() -> (Duration, Duration) {
+	let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+	// SAFETY: getrusage only writes to the passed struct
+	if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+		return (Duration::ZERO, Duration::ZERO);
+	}
+	// SAFETY: initialized by the successful call above
+	let usage = unsafe { usage.assume_init() };
+
+	let duration = |time: libc::timeval| Duration::new(time.tv_sec as u64, time.tv_usec as u32 * 1000);
+	(duration(usage.ru_utime), duration(usage.ru_stime))
+}
+
+#[cfg(not(unix))]
+fn cpu_times() -> (Duration, Duration) {
+	(Duration::ZERO, Duration::ZERO)
+}
+
+/// Runs `f` and measures its wall clock and CPU time.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Sample) {
+	let (user, system) = cpu_times();
+	let start = Instant::now();
+
+	let result = f();
+
+	let wall = start.elapsed();
+	let (user_end, system_end) = cpu_times();
+
+	(result, Sample { wall, user: user_end.saturating_sub(user), system: system_end.saturating_sub(system) })
+}
+
+/// Statistics over repeated runs, all values in seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+	pub runs: usize,
+	pub mean: f64,
+	/// Sample standard deviation, `0` for a single run.
+	pub stddev: f64,
+	pub median: f64,
+	/// Mean user CPU time.
+	pub user: f64,
+	/// Mean system CPU time.
+	pub system: f64,
+	pub min: f64,
+	pub max: f64
+}
+impl Stats {
+	pub fn from_samples(samples: &[Sample]) -> Option<Self> {
+		if samples.is_empty() {
+			return None;
+		}
+
+		let n = samples.len() as f64;
+		let mut wall: Vec<f64> = samples.iter().map(|s| s.wall.as_secs_f64()).collect();
+		wall.sort_by(f64::total_cmp);
+
+		let mean = wall.iter().sum::<f64>() / n;
+		let stddev = if samples.len() > 1 {
+			(wall.iter().map(|w| (w - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+		} else {
+			0.0
+		};
+		let median = if wall.len().is_multiple_of(2) {
+			(wall[wall.len() / 2 - 1] + wall[wall.len() / 2]) / 2.0
+		} else {
+			wall[wall.len() / 2]
+		};
+
+		Some(Self {
+			runs: samples.len(),
+			mean,
+			stddev,
+			median,
+			user: samples.iter().map(|s| s.user.as_secs_f64()).sum::<f64>() / n,
+			system: samples.iter().map(|s| s.system.as_secs_f64()).sum::<f64>() / n,
+			min: wall[0],
+			max: wall[wall.len() - 1]
+		})
+	}
+
+	/// Row of the benchmark CSV matching [`CSV_HEADER`].
+	pub fn csv_row(&self, command: &str) -> String {
+		format!(
+			"{},{},{},{},{},{},{},{}",
+			command, self.mean, self.stddev, self.median, self.user, self.system, self.min, self.max
+		)
+	}
+}
+impl fmt::Display for Stats {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"{} ± {} (min {}, max {}, {} runs)",
+			DisplaySeconds(self.mean), DisplaySeconds(self.stddev), DisplaySeconds(self.min), DisplaySeconds(self.max), self.runs
+		)
+	}
+}
+
+/// Formats seconds as `1.234ms`, `1.23s` or `1m5s`.
+#[derive(Debug, Clone, Copy)]
+pub struct DisplaySeconds(pub f64);
+impl fmt::Display for DisplaySeconds {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let s = self.0;
+		if s < 1.0 {
+			write!(f, "{:.3}ms", s * 1000.0)
+		} else if s < 60.0 {
+			write!(f, "{:.2}s", s)
+		} else {
+			write!(f, "{}m{:.0}s", (s / 60.0).floor(), s % 60.0)
+		}
+	}
+}
+
+/// Appends `(command, stats)` rows to the CSV at `path`, writing the header first if the file is new or empty.
+pub fn append_csv<'a>(path: &Path, rows: impl IntoIterator<Item = (&'a str, &'a Stats)>) -> anyhow::Result<()> {
+	let mut file = OpenOptions::new().append(true).create(true).open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+
+	let mut text = String::new();
+	if file.metadata().map(|m| m.len() == 0).unwrap_or(true) {
+		text.push_str(CSV_HEADER);
+		text.push('\n');
+	}
+	for (command, stats) in rows {
+		text.push_str(&stats.csv_row(command));
+		text.push('\n');
+	}
+	file.write_all(text.as_bytes()).with_context(|| format!("Failed to write {}", path.display()))?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use std::time::Duration;
+
+	use super::{append_csv, measure, DisplaySeconds, Sample, Stats, CSV_HEADER};
+
+	fn sample(millis: u64) -> Sample {
+		Sample { wall: Duration::from_millis(millis), user: Duration::from_millis(millis / 2), system: Duration::ZERO }
+	}
+
+	#[test]
+	fn test_stats() {
+		let stats = Stats::from_samples(&[sample(40), sample(10), sample(30), sample(20)]).unwrap();
+		assert_eq!(stats.runs, 4);
+		assert!((stats.mean - 0.025).abs() < 1e-9);
+		assert!((stats.median - 0.025).abs() < 1e-9);
+		assert!((stats.stddev - 0.012_909_944).abs() < 1e-6);
+		assert!((stats.user - 0.0125).abs() < 1e-9);
+		assert_eq!((stats.min, stats.max), (0.01, 0.04));
+
+		let stats = Stats::from_samples(&[sample(7)]).unwrap();
+		assert_eq!((stats.stddev, stats.median), (0.0, 0.007));
+		assert!(Stats::from_samples(&[]).is_none());
+
+		assert_eq!(stats.csv_row("puzzle01_parse").split(',').count(), CSV_HEADER.split(',').count());
+	}
+
+	#[test]
+	fn test_display_seconds() {
+		assert_eq!(DisplaySeconds(0.0012345).to_string(), "1.234ms");
+		assert_eq!(DisplaySeconds(2.345).to_string(), "2.35s");
+		assert_eq!(DisplaySeconds(65.2).to_string(), "1m5s");
+	}
+
+	#[test]
+	fn test_measure_and_csv() {
+		let (value, sample) = measure(|| (0 .. 1000u64).sum::<u64>());
+		assert_eq!(value, 499500);
+		assert!(sample.wall > Duration::ZERO);
+
+		let path = std::env::temp_dir().join(format!("aoc_commons_bench_{}.csv", std::process::id()));
+		let stats = Stats::from_samples(&[sample]).unwrap();
+		append_csv(&path, [("a", &stats)]).unwrap();
+		append_csv(&path, [("b", &stats), ("c", &stats)]).unwrap();
+
+		let text = std::fs::read_to_string(&path).unwrap();
+		let lines: Vec<&str> = text.lines().collect();
+		assert_eq!(lines.len(), 4);
+		assert_eq!(lines[0], CSV_HEADER);
+		assert!(lines[2].starts_with("b,"));
+
+		std::fs::remove_file(&path).unwrap();
+	}
+}
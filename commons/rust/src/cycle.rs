@@ -0,0 +1,164 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Steps `start + k * length` for all `k >= 0` have the same fingerprint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+	pub start: usize,
+	pub length: usize
+}
+
+/// Detects cycles in a simulation by remembering the step at which each fingerprint was first seen.
+///
+/// Step 0 is the initial state, each call to [`CycleDetector::push`] records the next step together with a tracked metric
+/// (e.g. tower height) which can be later extrapolated to any step count.
+#[derive(Debug, Clone)]
+pub struct CycleDetector<K> {
+	seen: HashMap<K, usize>,
+	metrics: Vec<i64>,
+	cycle: Option<Cycle>
+}
+impl<K: Eq + Hash> CycleDetector<K> {
+	pub fn new() -> Self {
+		Self { seen: HashMap::new(), metrics: Vec::new(), cycle: None }
+	}
+
+	/// Number of recorded steps.
+	pub fn steps(&self) -> usize {
+		self.metrics.len()
+	}
+
+	pub fn cycle(&self) -> Option<Cycle> {
+		self.cycle
+	}
+
+	/// Records the fingerprint and metric of the next step and returns the cycle once detected.
+	///
+	/// After a cycle is detected further pushes are ignored.
+	pub fn push(&mut self, fingerprint: K, metric: i64) -> Option<Cycle> {
+		if self.cycle.is_some() {
+			return self.cycle;
+		}
+
+		let step = self.metrics.len();
+		self.metrics.push(metric);
+
+		if let Some(&start) = self.seen.get(&fingerprint) {
+			self.cycle = Some(Cycle { start, length: step - start });
+		} else {
+			self.seen.insert(fingerprint, step);
+		}
+
+		self.cycle
+	}
+
+	/// Returns the metric at `step`, extrapolating from the detected cycle if it wasn't recorded.
+	///
+	/// The metric is assumed to change by the same amount over each cycle repetition.
+	pub fn extrapolate(&self, step: usize) -> anyhow::Result<i64> {
+		if let Some(&metric) = self.metrics.get(step) {
+			return Ok(metric);
+		}
+
+		let Cycle { start, length } = self.cycle.ok_or_else(
+			|| anyhow::anyhow!("No cycle detected within {} steps, cannot extrapolate to step {}", self.metrics.len(), step)
+		)?;
+
+		let per_cycle = self.metrics[start + length] as i128 - self.metrics[start] as i128;
+		let cycles = ((step - start) / length) as i128;
+		let offset = (step - start) % length;
+
+		let metric = self.metrics[start + offset] as i128 + cycles * per_cycle;
+		i64::try_from(metric).map_err(|_| anyhow::anyhow!("Extrapolated metric at step {} overflows", step))
+	}
+}
+impl<K: Eq + Hash> Default for CycleDetector<K> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Steps `state` until a cycle is detected or `max_steps` steps are taken.
+///
+/// `observe` returns the fingerprint and metric of the current state, it is also called on the initial state.
+pub fn detect<S, K: Eq + Hash>(
+	state: &mut S,
+	max_steps: usize,
+	mut step: impl FnMut(&mut S) -> anyhow::Result<()>,
+	mut observe: impl FnMut(&S) -> (K, i64)
+) -> anyhow::Result<CycleDetector<K>> {
+	let mut detector = CycleDetector::new();
+
+	let (fingerprint, metric) = observe(state);
+	detector.push(fingerprint, metric);
+
+	for _ in 0 .. max_steps {
+		step(state)?;
+
+		let (fingerprint, metric) = observe(state);
+		if detector.push(fingerprint, metric).is_some() {
+			break;
+		}
+	}
+
+	Ok(detector)
+}
+
+#[cfg(test)]
+mod test {
+	use super::{detect, Cycle, CycleDetector};
+
+	#[test]
+	fn test_cycle_detector() {
+		// 0 1 2 3 4 2 3 4 2 ...
+		let sequence = |step: usize| if step < 2 { step } else { 2 + (step - 2) % 3 };
+
+		let mut detector = CycleDetector::new();
+		let mut cycle = None;
+		for step in 0 .. 10 {
+			cycle = detector.push(sequence(step), step as i64 * 10);
+			if cycle.is_some() {
+				break;
+			}
+		}
+		assert_eq!(cycle, Some(Cycle { start: 2, length: 3 }));
+		assert_eq!(detector.steps(), 6);
+
+		assert_eq!(detector.extrapolate(4).unwrap(), 40);
+		assert_eq!(detector.extrapolate(1_000_000_000_000).unwrap(), 10_000_000_000_000);
+	}
+
+	#[test]
+	fn test_detect() {
+		// the metric gains 1 for each odd value of a sequence repeating with period 4 after 3 steps
+		let mut state = (0u64, 0i64);
+		let detector = detect(
+			&mut state,
+			100,
+			|(value, odd)| {
+				*value = if *value < 6 { *value + 1 } else { 3 };
+				*odd += (*value % 2) as i64;
+				Ok(())
+			},
+			|(value, odd)| (*value, *odd)
+		).unwrap();
+
+		assert_eq!(detector.cycle(), Some(Cycle { start: 3, length: 4 }));
+
+		let mut brute = (0u64, 0i64);
+		for step in 1 ..= 50 {
+			brute.0 = if brute.0 < 6 { brute.0 + 1 } else { 3 };
+			brute.1 += (brute.0 % 2) as i64;
+			assert_eq!(detector.extrapolate(step).unwrap(), brute.1);
+		}
+	}
+
+	#[test]
+	fn test_no_cycle() {
+		let mut state = 0u64;
+		let detector = detect(&mut state, 10, |value| { *value += 1; Ok(()) }, |value| (*value, *value as i64)).unwrap();
+
+		assert_eq!(detector.cycle(), None);
+		assert_eq!(detector.extrapolate(10).unwrap(), 10);
+		assert!(detector.extrapolate(11).is_err());
+	}
+}
@@ -1,32 +1,41 @@
 use std::{
+	collections::HashMap,
 	fmt,
-	ops::{Add, Sub, Mul, Neg, Range, Rem}
+	ops::{Add, AddAssign, Sub, SubAssign, Mul, Div, Neg, Range, Rem}
 };
 
+use anyhow::Context;
+
+use crate::numeric::Integer;
+
+/// Point with `D` coordinates of scalar type `T`.
+///
+/// The scalar is the second parameter so that `Point<2>` keeps meaning a point of `isize`s.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Point<const D: usize>([isize; D]);
-impl Point<1> {
-	pub const fn new(x: isize) -> Self {
+pub struct Point<const D: usize, T = isize>([T; D]);
+impl<T: Copy> Point<1, T> {
+	pub const fn new(x: T) -> Self {
 		Self([x])
 	}
 
-	pub const fn x(&self) -> isize {
+	pub const fn x(&self) -> T {
 		self.0[0]
 	}
 }
-impl Point<2> {
-	pub const fn new(x: isize, y: isize) -> Self {
+impl<T: Copy> Point<2, T> {
+	pub const fn new(x: T, y: T) -> Self {
 		Self([x, y])
 	}
 
-	pub const fn x(&self) -> isize {
+	pub const fn x(&self) -> T {
 		self.0[0]
 	}
 
-	pub const fn y(&self) -> isize {
+	pub const fn y(&self) -> T {
 		self.0[1]
 	}
-
+}
+impl Point<2> {
 	pub fn rot2d_clockwise(self) -> Self {
 		let sx = self.0[0].signum();
 		let sy = self.0[1].signum();
@@ -37,113 +46,243 @@ impl Point<2> {
 		])
 	}
 }
-impl Point<3> {
-	pub const fn new(x: isize, y: isize, z: isize) -> Self {
+impl<T: Integer + Neg<Output = T>> Point<2, T> {
+	/// Rotates by `turns` quarter turns around the origin, positive turns go from the +x axis towards the +y axis.
+	///
+	/// With y pointing down (as in grids) a positive turn is clockwise.
+	pub fn rot90(self, turns: isize) -> Self {
+		let [x, y] = self.0;
+		match turns.rem_euclid(4) {
+			0 => self,
+			1 => Self([-y, x]),
+			2 => Self([-x, -y]),
+			_ => Self([y, -x])
+		}
+	}
+
+	/// 2D cross product (z component of the 3D cross product), positive when `rhs` is a positive turn from `self`.
+	pub fn perp_dot(self, rhs: Self) -> T {
+		self.0[0] * rhs.0[1] - self.0[1] * rhs.0[0]
+	}
+
+	/// Diagonal coordinates `(x + y, x - y)`, in which Manhattan diamonds become axis aligned squares.
+	pub fn to_diagonal(self) -> Self {
+		let [x, y] = self.0;
+		Self([x + y, x - y])
+	}
+
+	/// Inverse of [`Point::to_diagonal`], `None` for diagonal coordinates of different parity which have no point.
+	pub fn from_diagonal(self) -> Option<Self> {
+		let [u, v] = self.0;
+		let two = T::ONE + T::ONE;
+		if (u + v) % two != T::ZERO {
+			return None;
+		}
+
+		Some(Self([(u + v) / two, (u - v) / two]))
+	}
+}
+impl<T: Copy> Point<3, T> {
+	pub const fn new(x: T, y: T, z: T) -> Self {
 		Self([x, y, z])
 	}
 
-	pub const fn x(&self) -> isize {
+	pub const fn x(&self) -> T {
 		self.0[0]
 	}
 
-	pub const fn y(&self) -> isize {
+	pub const fn y(&self) -> T {
 		self.0[1]
 	}
 
-	pub const fn z(&self) -> isize {
+	pub const fn z(&self) -> T {
 		self.0[2]
 	}
 }
-impl<const D: usize> Point<D> {
-	pub const MIN: Self = Self([isize::MIN; D]);
-	pub const ZERO: Self = Self([0; D]);
-	pub const MAX: Self = Self([isize::MAX; D]);
+impl<T: Integer> Point<3, T> {
+	pub fn cross(self, rhs: Self) -> Self {
+		let [ax, ay, az] = self.0;
+		let [bx, by, bz] = rhs.0;
+
+		Self([ay * bz - az * by, az * bx - ax * bz, ax * by - ay * bx])
+	}
+}
+impl<const D: usize, T: Integer> Point<D, T> {
+	pub const MIN: Self = Self([T::MIN; D]);
+	pub const ZERO: Self = Self([T::ZERO; D]);
+	pub const MAX: Self = Self([T::MAX; D]);
 
-	pub fn data(&self) -> &[isize; D] {
+	pub fn data(&self) -> &[T; D] {
 		&self.0
 	}
 
 	pub fn min(self, other: Self) -> Self {
-		let mut v = [0; D];
-		for i in 0 .. D {
-			v[i] = self.0[i].min(other.0[i]);
-		}
-
-		Self(v)
+		Self(std::array::from_fn(|i| self.0[i].min(other.0[i])))
 	}
 
 	pub fn max(self, other: Self) -> Self {
-		let mut v = [0; D];
-		for i in 0 .. D {
-			v[i] = self.0[i].max(other.0[i]);
+		Self(std::array::from_fn(|i| self.0[i].max(other.0[i])))
+	}
+
+	pub fn dot(self, rhs: Self) -> T {
+		self.0.iter().zip(rhs.0.iter()).fold(T::ZERO, |acc, (&a, &b)| acc + a * b)
+	}
+
+	pub fn length_manhattan(self) -> T {
+		self.0.iter().fold(T::ZERO, |acc, v| acc + v.abs())
+	}
+
+	/// Largest absolute coordinate, the number of king moves needed to reach this point.
+	pub fn length_chebyshev(self) -> T {
+		self.0.iter().map(|v| v.abs()).max().unwrap_or(T::ZERO)
+	}
+
+	/// Squared euclidean length, exact unlike the length itself.
+	pub fn length_squared(self) -> T {
+		self.dot(self)
+	}
+
+	pub fn checked_add(self, rhs: Self) -> Option<Self> {
+		self.checked_zip(rhs, T::checked_add)
+	}
+
+	pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+		self.checked_zip(rhs, T::checked_sub)
+	}
+
+	pub fn checked_mul(self, rhs: T) -> Option<Self> {
+		self.checked_zip(Self([rhs; D]), T::checked_mul)
+	}
+
+	pub fn saturating_add(self, rhs: Self) -> Self {
+		Self(std::array::from_fn(|i| self.0[i].saturating_add(rhs.0[i])))
+	}
+
+	pub fn saturating_sub(self, rhs: Self) -> Self {
+		Self(std::array::from_fn(|i| self.0[i].saturating_sub(rhs.0[i])))
+	}
+
+	fn checked_zip(self, rhs: Self, op: impl Fn(T, T) -> Option<T>) -> Option<Self> {
+		let mut result = self.0;
+		for (v, rhs) in result.iter_mut().zip(rhs.0) {
+			*v = op(*v, rhs)?;
 		}
 
-		Self(v)
+		Some(Self(result))
 	}
 
+	/// Converts coordinates to a wider scalar type.
+	pub fn cast<U: From<T>>(self) -> Point<D, U> {
+		Point(self.0.map(U::from))
+	}
+
+	/// Converts coordinates to another scalar type, `None` if any of them does not fit.
+	pub fn try_cast<U: Integer>(self) -> Option<Point<D, U>> {
+		let mut result = [U::ZERO; D];
+		for (v, from) in result.iter_mut().zip(self.0) {
+			*v = U::from_i128(from.to_i128())?;
+		}
+
+		Some(Point(result))
+	}
+}
+impl<const D: usize> Point<D> {
+	/// Iterates the `2 * D` points differing by one in exactly one coordinate (4 in 2D, 6 in 3D).
+	pub fn neighbors_orthogonal(self) -> impl Iterator<Item = Self> {
+		(0 .. 2 * D).map(move |i| {
+			let mut point = self;
+			point.0[i / 2] += if i % 2 == 0 { -1 } else { 1 };
+
+			point
+		})
+	}
+
+	/// Iterates the `3^D - 1` points differing by at most one in every coordinate (8 in 2D, 26 in 3D).
+	pub fn neighbors_all(self) -> impl Iterator<Item = Self> {
+		let count = 3usize.pow(D as u32);
+
+		(0 .. count).filter(move |&i| i != count / 2).map(move |i| {
+			let mut rest = i;
+			let mut point = self;
+			for v in point.0.iter_mut() {
+				*v += (rest % 3) as isize - 1;
+				rest /= 3;
+			}
 
-	pub fn length_manhattan(self) -> isize {
-		self.0.map(|v| v.abs()).into_iter().sum()
+			point
+		})
+	}
+}
+impl<const D: usize, T> From<[T; D]> for Point<D, T> {
+	fn from(value: [T; D]) -> Self {
+		Self(value)
+	}
+}
+impl<const D: usize, T> From<Point<D, T>> for [T; D] {
+	fn from(value: Point<D, T>) -> Self {
+		value.0
 	}
 }
-impl<const D: usize> Neg for Point<D> {
+impl<const D: usize, T: Integer + Neg<Output = T>> Neg for Point<D, T> {
 	type Output = Self;
 
 	fn neg(self) -> Self::Output {
 		Self(self.0.map(|v| -v))
 	}
 }
-impl<const D: usize> Add<Self> for Point<D> {
+impl<const D: usize, T: Integer> Add<Self> for Point<D, T> {
 	type Output = Self;
 
 	fn add(self, rhs: Self) -> Self::Output {
-		let mut v = [0; D];
-		for i in 0 .. D {
-			v[i] = self.0[i] + rhs.0[i];
-		}
-
-		Self(v)
+		Self(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
 	}
 }
-impl<const D: usize> Sub<Self> for Point<D> {
+impl<const D: usize, T: Integer> AddAssign<Self> for Point<D, T> {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs;
+	}
+}
+impl<const D: usize, T: Integer> Sub<Self> for Point<D, T> {
 	type Output = Self;
 
 	fn sub(self, rhs: Self) -> Self::Output {
-		let mut v = [0; D];
-		for i in 0 .. D {
-			v[i] = self.0[i] - rhs.0[i];
-		}
-
-		Self(v)
+		Self(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+	}
+}
+impl<const D: usize, T: Integer> SubAssign<Self> for Point<D, T> {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs;
 	}
 }
-impl<const D: usize> Rem<Self> for Point<D> {
+impl<const D: usize, T: Integer> Rem<Self> for Point<D, T> {
 	type Output = Self;
 
 	fn rem(self, rhs: Self) -> Self::Output {
-		let mut v = [0; D];
-		for i in 0 .. D {
-			v[i] = self.0[i] % rhs.0[i];
-		}
-
-		Self(v)
+		Self(std::array::from_fn(|i| self.0[i] % rhs.0[i]))
 	}
 }
-impl<const D: usize> Mul<isize> for Point<D> {
+impl<const D: usize, T: Integer> Mul<T> for Point<D, T> {
 	type Output = Self;
 
-	fn mul(self, rhs: isize) -> Self::Output {
+	fn mul(self, rhs: T) -> Self::Output {
 		Self(self.0.map(|v| v * rhs))
 	}
 }
-impl<const D: usize> Rem<isize> for Point<D> {
+impl<const D: usize, T: Integer> Div<T> for Point<D, T> {
 	type Output = Self;
 
-	fn rem(self, rhs: isize) -> Self::Output {
+	fn div(self, rhs: T) -> Self::Output {
+		Self(self.0.map(|v| v / rhs))
+	}
+}
+impl<const D: usize, T: Integer> Rem<T> for Point<D, T> {
+	type Output = Self;
+
+	fn rem(self, rhs: T) -> Self::Output {
 		Self(self.0.map(|v| v % rhs))
 	}
 }
-impl<const D: usize> fmt::Display for Point<D> {
+impl<const D: usize, T: fmt::Display> fmt::Display for Point<D, T> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		if D == 0 {
 			return write!(f, "()");
@@ -161,98 +300,107 @@ impl<const D: usize> fmt::Display for Point<D> {
 	}
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Rectangle<const D: usize> {
-	pub min: Point<D>,
-	pub max: Point<D>
-}
-impl Rectangle<2> {
-	pub fn points_iter(&self) -> impl Iterator<Item = Point<2>> {
-		struct PointsIter {
-			min: Point<2>, max: Point<2>,
-			x: isize, y: isize
-		}
-		impl PointsIter {
-			pub fn new(rectangle: Rectangle<2>) -> Self {
-				Self { min: rectangle.min, max: rectangle.max, x: rectangle.min.x(), y: rectangle.min.y() }
-			}
-		}
-		impl Iterator for PointsIter {
-			type Item = Point<2>;
-
-			#[inline(always)]
-			fn next(&mut self) -> Option<Self::Item> {
-				if self.y >= self.max.y() {
-					return None;
+/// Rotation of the 3D grid which maps axes onto axes, one of the 24 orientations of a cube.
+///
+/// Stored as a signed permutation matrix with determinant 1, so rotations are exact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation3([[isize; 3]; 3]);
+impl Rotation3 {
+	pub const IDENTITY: Self = Self([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+	/// Quarter turn around the x axis, from +y towards +z.
+	pub const X: Self = Self([[1, 0, 0], [0, 0, -1], [0, 1, 0]]);
+	/// Quarter turn around the y axis, from +z towards +x.
+	pub const Y: Self = Self([[0, 0, 1], [0, 1, 0], [-1, 0, 0]]);
+	/// Quarter turn around the z axis, from +x towards +y.
+	pub const Z: Self = Self([[0, -1, 0], [1, 0, 0], [0, 0, 1]]);
+
+	/// All 24 rotations, starting with [`Rotation3::IDENTITY`].
+	pub fn all() -> [Self; 24] {
+		const PERMUTATIONS: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+
+		let mut result = [Self::IDENTITY; 24];
+		let mut count = 0;
+		for permutation in PERMUTATIONS {
+			for signs in 0 .. 8 {
+				let mut matrix = [[0; 3]; 3];
+				for (row, &column) in permutation.iter().enumerate() {
+					matrix[row][column] = if signs & (1 << row) == 0 { 1 } else { -1 };
 				}
 
-				let result = Point::<2>::new(self.x, self.y);
-
-				self.x += 1;
-				if self.x >= self.max.x() {
-					self.x = self.min.x();
-					self.y += 1;
+				let rotation = Self(matrix);
+				if rotation.determinant() == 1 {
+					result[count] = rotation;
+					count += 1;
 				}
-
-				Some(result)
 			}
 		}
 
-		PointsIter::new(*self)
+		result
 	}
-}
-impl Rectangle<3> {
-	pub fn points_iter(&self) -> impl Iterator<Item = Point<3>> {
-		struct PointsIter {
-			min: Point<3>, max: Point<3>,
-			at: Point<3>
-		}
-		impl PointsIter {
-			pub fn new(rectangle: Rectangle<3>) -> Self {
-				Self { min: rectangle.min, max: rectangle.max, at: rectangle.min }
-			}
-		}
-		impl Iterator for PointsIter {
-			type Item = Point<3>;
 
-			#[inline(always)]
-			fn next(&mut self) -> Option<Self::Item> {
-				if self.at.z() >= self.max.z() {
-					return None;
-				}
+	pub fn matrix(&self) -> &[[isize; 3]; 3] {
+		&self.0
+	}
 
-				let at = self.at;
+	pub fn determinant(&self) -> isize {
+		let [a, b, c] = self.0;
 
-				self.at.0[0] += 1;
-				if self.at.x() >= self.max.x() {
-					self.at.0[0] = self.min.x();
-					self.at.0[1] += 1;
+		Point(a).dot(Point(b).cross(Point(c)))
+	}
 
-					if self.at.y() >= self.max.y() {
-						self.at.0[1] = self.min.y();
-						self.at.0[2] += 1;
-					}
-				}
+	pub fn apply(&self, point: Point<3>) -> Point<3> {
+		Point(self.0.map(|row| Point(row).dot(point)))
+	}
 
-				Some(at)
-			}
-		}
+	/// Rotation which applies `rhs` first and then `self`.
+	pub fn compose(&self, rhs: &Self) -> Self {
+		Self(std::array::from_fn(
+			|row| std::array::from_fn(|column| (0 .. 3).map(|i| self.0[row][i] * rhs.0[i][column]).sum())
+		))
+	}
 
-		PointsIter::new(*self)
+	/// The inverse rotation, which is the transpose for orthogonal matrices.
+	pub fn inverse(&self) -> Self {
+		Self(std::array::from_fn(|row| std::array::from_fn(|column| self.0[column][row])))
 	}
 }
-impl<const D: usize> Rectangle<D> {
-	pub fn size(&self) -> Point<D> {
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rectangle<const D: usize, T = isize> {
+	pub min: Point<D, T>,
+	pub max: Point<D, T>
+}
+impl<const D: usize, T: Integer> Rectangle<D, T> {
+	/// Canonical empty rectangle, returned by operations whose result contains no points.
+	pub const EMPTY: Self = Self { min: Point::ZERO, max: Point::ZERO };
+
+	/// Smallest rectangle containing all `points`, [`Rectangle::EMPTY`] for no points.
+	pub fn from_points(points: impl IntoIterator<Item = Point<D, T>>) -> Self {
+		let mut points = points.into_iter();
+		let Some(first) = points.next() else {
+			return Self::EMPTY;
+		};
+
+		let (min, max) = points.fold((first, first), |(min, max), p| (min.min(p), max.max(p)));
+		Self { min, max: max + Point([T::ONE; D]) }
+	}
+
+	/// Whether the rectangle contains no points, that is `max <= min` in any dimension.
+	pub fn is_empty(&self) -> bool {
+		self.min.0.iter().zip(self.max.0.iter()).any(|(min, max)| max <= min)
+	}
+
+	pub fn size(&self) -> Point<D, T> {
 		self.max - self.min
 	}
 
 	pub fn area(&self) -> usize {
-		let size = self.size();
-
-		size.0.map(|v| v.max(0) as usize).into_iter().product()
+		self.min.0.iter().zip(self.max.0.iter()).map(
+			|(&min, &max)| if max > min { (max.to_i128() - min.to_i128()) as usize } else { 0 }
+		).product()
 	}
 
-	pub fn contains(&self, point: &Point<D>) -> bool {		
+	pub fn contains(&self, point: &Point<D, T>) -> bool {		
 		for i in 0 .. D {
 			if !(point.0[i] >= self.min.0[i] && point.0[i] < self.max.0[i]) {
 				return false;
@@ -263,44 +411,117 @@ impl<const D: usize> Rectangle<D> {
 	}
 
 	pub fn intersection(self, rhs: &Self) -> Self {
-		let min = self.min.max(rhs.min);
-		let max = self.max.min(rhs.max);
+		let result = Self { min: self.min.max(rhs.min), max: self.max.min(rhs.max) };
 
-		Self { min, max }
+		if result.is_empty() { Self::EMPTY } else { result }
 	}
 
-	pub fn wrap_around(&self, point: Point<D>) -> Point<D> {
+	/// Smallest rectangle containing both rectangles, empty rectangles are ignored.
+	pub fn bounding_union(self, rhs: &Self) -> Self {
+		match (self.is_empty(), rhs.is_empty()) {
+			(true, true) => Self::EMPTY,
+			(true, false) => *rhs,
+			(false, true) => self,
+			(false, false) => Self { min: self.min.min(rhs.min), max: self.max.max(rhs.max) }
+		}
+	}
+
+	/// Splits the points of `self` which are not in `rhs` into at most `2 * D` disjoint non-empty rectangles.
+	pub fn subtract(&self, rhs: &Self) -> Vec<Self> {
+		if self.is_empty() {
+			return Vec::new();
+		}
+
+		let cut = self.intersection(rhs);
+		if cut.is_empty() {
+			return vec![*self];
+		}
+
+		// slice off the parts below and above the cut one dimension at a time, shrinking the rest towards the cut
+		let mut result = Vec::new();
+		let mut rest = *self;
+		for i in 0 .. D {
+			let mut below = rest;
+			below.max.0[i] = cut.min.0[i];
+			let mut above = rest;
+			above.min.0[i] = cut.max.0[i];
+
+			result.extend([below, above].into_iter().filter(|r| !r.is_empty()));
+			rest.min.0[i] = cut.min.0[i];
+			rest.max.0[i] = cut.max.0[i];
+		}
+
+		result
+	}
+
+	/// Iterates all points of the rectangle, dimension 0 changes the fastest.
+	pub fn points_iter(&self) -> impl Iterator<Item = Point<D, T>> {
+		struct PointsIter<const D: usize, T> {
+			min: Point<D, T>, max: Point<D, T>,
+			at: Option<Point<D, T>>
+		}
+		impl<const D: usize, T: Integer> Iterator for PointsIter<D, T> {
+			type Item = Point<D, T>;
+
+			#[inline(always)]
+			fn next(&mut self) -> Option<Self::Item> {
+				let result = self.at?;
+
+				self.at = None;
+				let mut next = result;
+				for i in 0 .. D {
+					next.0[i] = next.0[i] + T::ONE;
+					if next.0[i] < self.max.0[i] {
+						self.at = Some(next);
+						break;
+					}
+					next.0[i] = self.min.0[i];
+				}
+
+				Some(result)
+			}
+		}
+
+		PointsIter { min: self.min, max: self.max, at: if self.is_empty() { None } else { Some(self.min) } }
+	}
+
+	pub fn wrap_around(&self, point: Point<D, T>) -> Point<D, T> {
 		if self.contains(&point) {
 			return point;
 		}
 
 		let size = self.size();
-		let relative = point - self.min + size; // TODO: hotfix - (-1 % x = -1)
+		let relative = point - self.min;
+
+		self.min + Point(std::array::from_fn(|i| relative.0[i].rem_euclid(size.0[i])))
+	}
 
-		self.min + (relative % size)
+	/// Moves `point` by `step`, wrapping around the edges as if the rectangle was a torus.
+	pub fn step_wrapping(&self, point: Point<D, T>, step: Point<D, T>) -> Point<D, T> {
+		self.wrap_around(point + step)
 	}
 }
-impl<const D: usize> Add<Point<D>> for Rectangle<D> {
+impl<const D: usize, T: Integer> Add<Point<D, T>> for Rectangle<D, T> {
 	type Output = Self;
 
-	fn add(self, rhs: Point<D>) -> Self::Output {
+	fn add(self, rhs: Point<D, T>) -> Self::Output {
 		Self {
 			min: self.min + rhs,
 			max: self.max + rhs
 		}
 	}
 }
-impl<const D: usize> Sub<Point<D>> for Rectangle<D> {
+impl<const D: usize, T: Integer> Sub<Point<D, T>> for Rectangle<D, T> {
 	type Output = Self;
 
-	fn sub(self, rhs: Point<D>) -> Self::Output {
+	fn sub(self, rhs: Point<D, T>) -> Self::Output {
 		Self {
 			min: self.min - rhs,
 			max: self.max - rhs
 		}
 	}
 }
-impl<const D: usize> fmt::Display for Rectangle<D> {
+impl<const D: usize, T: fmt::Display> fmt::Display for Rectangle<D, T> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(f, "[{}, {}]", self.min, self.max)
 	}
@@ -335,6 +556,56 @@ impl Circle2 {
 		(point - self.center).length_manhattan() <= self.radius
 	}
 
+	pub fn on_boundary(&self, point: Point<2>) -> bool {
+		(point - self.center).length_manhattan() == self.radius
+	}
+
+	/// Range of x coordinates covered on row `y`, `None` if the row misses the diamond.
+	pub fn row_range(&self, y: isize) -> Option<Range<isize>> {
+		let half_width = self.radius - (y - self.center.y()).abs();
+		(half_width >= 0).then(|| self.center.x() - half_width .. self.center.x() + half_width + 1)
+	}
+
+	/// Range of y coordinates covered on column `x`, `None` if the column misses the diamond.
+	pub fn column_range(&self, x: isize) -> Option<Range<isize>> {
+		let half_height = self.radius - (x - self.center.x()).abs();
+		(half_height >= 0).then(|| self.center.y() - half_height .. self.center.y() + half_height + 1)
+	}
+
+	/// The diamond as a square in diagonal coordinates (see [`Point::to_diagonal`]).
+	///
+	/// Unions and intersections of diamonds become those of rectangles, but only diagonal points
+	/// with coordinates of the same parity map back to points.
+	pub fn diagonal_box(&self) -> Rectangle<2> {
+		let center = self.center.to_diagonal();
+		let radius = Point::<2>::new(self.radius, self.radius);
+
+		Rectangle { min: center - radius, max: center + radius + Point::<2>::new(1, 1) }
+	}
+
+	/// Points where an edge of the boundary of one diamond crosses a perpendicular edge of the other.
+	///
+	/// Parallel edges which overlap only contribute the points where they meet perpendicular edges.
+	pub fn boundary_crossings(&self, other: &Self) -> Vec<Point<2>> {
+		let mut result = Vec::new();
+		for (first, second) in [(self, other), (other, self)] {
+			let first_center = first.center.to_diagonal();
+			let second_center = second.center.to_diagonal();
+
+			for u in [first_center.x() - first.radius, first_center.x() + first.radius] {
+				for v in [second_center.y() - second.radius, second_center.y() + second.radius] {
+					let Some(point) = Point::<2>::new(u, v).from_diagonal() else { continue };
+
+					if self.on_boundary(point) && other.on_boundary(point) && !result.contains(&point) {
+						result.push(point);
+					}
+				}
+			}
+		}
+
+		result
+	}
+
 	pub fn disk_points(&self) -> impl Iterator<Item = Point<2>> {
 		struct DiskPointsIter {
 			center: Point<2>,
@@ -345,7 +616,7 @@ impl Circle2 {
 
 			fn next(&mut self) -> Option<Self::Item> {
 				if self.current.x() == 0 && self.current.y() == 0 {
-					return None;
+					None
 				} else {
 					let result = self.current;
 					
@@ -365,6 +636,7 @@ impl Circle2 {
 		}
 	}
 
+	/// Smallest rectangle containing the whole diamond.
 	pub fn bounding_box(&self) -> Rectangle<2> {
 		Rectangle {
 			min: -Point::<2>::new(self.radius, self.radius),
@@ -372,6 +644,7 @@ impl Circle2 {
 		} + self.center
 	}
 
+	/// Largest square centered on the diamond which is inside of it.
 	pub fn inner_bounding_box(&self) -> Rectangle<2> {
 		Rectangle {
 			min: -Point::<2>::new(self.radius / 2, self.radius / 2),
@@ -385,17 +658,24 @@ impl fmt::Display for Circle2 {
 	}
 }
 
-/*
 #[derive(Debug, Clone)]
 pub struct Grid<T, const D: usize> {
 	cells: Vec<T>,
-	bounding_box: Rectangle<D>
+	bounding_box: Rectangle<D>,
+	/// Creates cells when [`Grid::get_mut`] grows the grid, see [`Grid::set_growing`].
+	grow_fill: Option<fn() -> T>
 }
+pub type Grid2<T> = Grid<T, 2>;
+pub type Grid3<T> = Grid<T, 3>;
 impl<T, const D: usize> Grid<T, D> {
 	pub fn new(cells: Vec<T>, bounding_box: Rectangle<D>) -> anyhow::Result<Self> {
-		anyhow::ensure!(bounding_box.area() as usize == cells.len(), "bounding_box must define the same size as cells len");
+		let size = bounding_box.size();
+		anyhow::ensure!(
+			size.0.iter().map(|v| v.unsigned_abs()).product::<usize>() == cells.len(),
+			"bounding_box must define the same size as cells len"
+		);
 
-		Ok(Self { cells, bounding_box })
+		Ok(Self { cells, bounding_box, grow_fill: None })
 	}
 
 	pub fn bounding_box(&self) -> Rectangle<D> {
@@ -406,8 +686,37 @@ impl<T, const D: usize> Grid<T, D> {
 		self.bounding_box.min.0[dim] .. self.bounding_box.max.0[dim]
 	}
 
-	fn index(at: Point<D>, size: Point<D>) -> usize {
-		std::iter::once(1).chain(size.data()).
+	/// Row-major index of a point relative to the bounding box min - dimension 0 changes the fastest.
+	fn index(at: Point<D>, size: Point<D>) -> Option<usize> {
+		let mut index = 0;
+		let mut stride = 1;
+		for (&at, &size) in at.0.iter().zip(size.0.iter()) {
+			if at < 0 || at >= size {
+				return None;
+			}
+
+			index += at as usize * stride;
+			stride *= size as usize;
+		}
+
+		Some(index)
+	}
+
+	pub fn index_to_coords(&self, index: usize) -> Option<Point<D>> {
+		if index >= self.cells.len() {
+			return None;
+		}
+
+		let size = self.bounding_box.size();
+		let mut rest = index;
+		let mut relative = [0; D];
+		for (v, size) in relative.iter_mut().zip(size.0) {
+			let size = size.unsigned_abs();
+			*v = (rest % size) as isize;
+			rest /= size;
+		}
+
+		Some(self.bounding_box.min + Point(relative))
 	}
 
 	pub fn get(&self, at: Point<D>) -> Option<&T> {
@@ -416,42 +725,117 @@ impl<T, const D: usize> Grid<T, D> {
 	}
 
 	pub fn get_relative(&self, at: Point<D>) -> Option<&T> {
-		let size = self.bounding_box.size();
-		if at.data().into_iter().zip(size.data().into_iter()).any(|(&at, &size)| at < 0 || at >= size) {
-			return None;
-		}
-
-		Some(&self.cells[at.x() as usize + at.y() as usize * size.x().abs() as usize])
+		Self::index(at, self.bounding_box.size()).map(|index| &self.cells[index])
 	}
 
+	/// Mutable access to the cell at `at`, growing the grid first if it was set to grow with [`Grid::set_growing`].
 	pub fn get_mut(&mut self, at: Point<D>) -> Option<&mut T> {
+		if let Some(fill) = self.grow_fill {
+			self.grow_with(at, fill);
+		}
+
 		let at = at - self.bounding_box.min;
 		self.get_mut_relative(at)
 	}
 
 	pub fn get_mut_relative(&mut self, at: Point<D>) -> Option<&mut T> {
-		let size = self.bounding_box.size();
-		if at.x() < 0 || at.x() >= size.x() || at.y() < 0 || at.y() >= size.y() {
-			return None;
+		Self::index(at, self.bounding_box.size()).map(|index| &mut self.cells[index])
+	}
+
+	/// Makes [`Grid::get_mut`] grow the grid to contain any point it is called with, new cells are created by `fill`.
+	///
+	/// [`Grid::get`] and other read-only methods never grow the grid.
+	pub fn set_growing(&mut self, fill: Option<fn() -> T>) {
+		self.grow_fill = fill;
+	}
+
+	/// Grows the grid to contain `at`, new cells are clones of `fill`. Coordinates of existing cells do not change.
+	pub fn ensure_contains(&mut self, at: Point<D>, fill: T) where T: Clone {
+		self.grow_with(at, || fill.clone());
+	}
+
+	/// Reallocates the grid so that it contains `at`.
+	///
+	/// Each side that needs to grow grows by at least the current size, so growing by one cell at a time is amortized.
+	fn grow_with(&mut self, at: Point<D>, mut fill: impl FnMut() -> T) {
+		if self.bounding_box.contains(&at) {
+			return;
 		}
 
-		Some(&mut self.cells[at.x() as usize + at.y() as usize * size.x().abs() as usize])
+		let old_box = self.bounding_box;
+		let size = old_box.size();
+		let mut bounding_box = old_box;
+		for i in 0 .. D {
+			let headroom = size.0[i].max(1);
+			if at.0[i] < old_box.min.0[i] {
+				bounding_box.min.0[i] = at.0[i].min(old_box.min.0[i] - headroom);
+			}
+			if at.0[i] >= old_box.max.0[i] {
+				bounding_box.max.0[i] = (at.0[i] + 1).max(old_box.max.0[i] + headroom);
+			}
+		}
+
+		// both boxes are iterated in index order, so the old cells come in the order they are needed
+		let mut old_cells = std::mem::take(&mut self.cells).into_iter();
+		self.cells = bounding_box.points_iter().map(
+			|p| if old_box.contains(&p) { old_cells.next().unwrap() } else { fill() }
+		).collect();
+		self.bounding_box = bounding_box;
 	}
 
 	pub fn shift(&mut self, shift: Point<D>) {
 		self.bounding_box = self.bounding_box + shift;
 	}
+
+	/// Like [`Grid::get`], but coordinates outside of the grid wrap around.
+	pub fn get_wrapping(&self, at: Point<D>) -> Option<&T> {
+		if self.cells.is_empty() {
+			return None;
+		}
+
+		self.get(self.bounding_box.wrap_around(at))
+	}
+
+	/// Iterates orthogonal neighbors of `at` which are inside the grid, together with their cells.
+	pub fn neighbors_orthogonal(&self, at: Point<D>) -> impl Iterator<Item = (Point<D>, &T)> {
+		at.neighbors_orthogonal().filter_map(move |p| self.get(p).map(|cell| (p, cell)))
+	}
+
+	/// Iterates all neighbors (including diagonal) of `at` which are inside the grid, together with their cells.
+	pub fn neighbors_all(&self, at: Point<D>) -> impl Iterator<Item = (Point<D>, &T)> {
+		at.neighbors_all().filter_map(move |p| self.get(p).map(|cell| (p, cell)))
+	}
+
+	/// Iterates orthogonal neighbors of `at`, wrapping around the edges of the grid.
+	pub fn neighbors_orthogonal_wrapping(&self, at: Point<D>) -> impl Iterator<Item = (Point<D>, &T)> {
+		at.neighbors_orthogonal().filter(|_| !self.cells.is_empty()).map(move |p| {
+			let p = self.bounding_box.wrap_around(p);
+			(p, self.get(p).unwrap())
+		})
+	}
+
+	/// Iterates all neighbors (including diagonal) of `at`, wrapping around the edges of the grid.
+	pub fn neighbors_all_wrapping(&self, at: Point<D>) -> impl Iterator<Item = (Point<D>, &T)> {
+		at.neighbors_all().filter(|_| !self.cells.is_empty()).map(move |p| {
+			let p = self.bounding_box.wrap_around(p);
+			(p, self.get(p).unwrap())
+		})
+	}
 }
- */
+impl<T: Copy, const D: usize> Grid<T, D> {
+	pub fn new_fill(fill: T, bounding_box: Rectangle<D>) -> anyhow::Result<Self> {
+		Self::new(vec![fill; bounding_box.area()], bounding_box)
+	}
 
-#[derive(Debug, Clone)]
-pub struct Grid2<T> {
-	cells: Vec<T>,
-	bounding_box: Rectangle<2>
+	pub fn fill(&mut self, fill: T) {
+		for cell in self.cells.iter_mut() {
+			*cell = fill;
+		}
+	}
 }
-impl<T> Grid2<T> {
+impl<T> Grid<T, 2> {
 	pub fn new_width(cells: Vec<T>, width: isize) -> anyhow::Result<Self> {
-		anyhow::ensure!(width != 0 && cells.len() > 0 && (cells.len() % width.abs() as usize == 0), "cells len must be divisible by width");
+		anyhow::ensure!(width != 0 && !cells.is_empty() && cells.len().is_multiple_of(width.unsigned_abs()), "cells len must be divisible by width");
 
 		let height = cells.len() as isize / width;
 		let bounding_box = if width < 0 {
@@ -469,15 +853,78 @@ impl<T> Grid2<T> {
 		Self::new(cells, bounding_box)
 	}
 
-	pub fn new(cells: Vec<T>, bounding_box: Rectangle<2>) -> anyhow::Result<Self> {
-		let size = bounding_box.size();
-		anyhow::ensure!(size.x().abs() as usize * size.y().abs() as usize == cells.len(), "bounding_box must define the same size as cells len");
+	/// Parses a grid of chars where each line is a row, all lines must have the same length.
+	///
+	/// Parsing stops at the first empty line, so the grid section of a larger input can be passed directly.
+	pub fn parse(input: &str) -> anyhow::Result<Self> where T: TryFrom<char>, T::Error: Into<anyhow::Error> {
+		Self::parse_with(input, |ch| T::try_from(ch).map_err(Into::into))
+	}
 
-		Ok(Self { cells, bounding_box: bounding_box })
+	/// Like [`Grid::parse`], but cells are converted using `convert` instead of `TryFrom<char>`.
+	pub fn parse_with(input: &str, mut convert: impl FnMut(char) -> anyhow::Result<T>) -> anyhow::Result<Self> {
+		Self::parse_inner(input, None, &[], &mut convert).map(|(grid, _)| grid)
 	}
 
-	pub fn bounding_box(&self) -> Rectangle<2> {
-		self.bounding_box
+	/// Like [`Grid::parse`], but lines shorter than the longest line are padded with `fill`.
+	pub fn parse_padded(input: &str, fill: T) -> anyhow::Result<Self> where T: Clone + TryFrom<char>, T::Error: Into<anyhow::Error> {
+		Self::parse_inner(input, Some(&|| fill.clone()), &[], &mut |ch| T::try_from(ch).map_err(Into::into)).map(|(grid, _)| grid)
+	}
+
+	/// Like [`Grid::parse`], but also returns the positions of `markers` chars. Each marker must appear exactly once.
+	///
+	/// Marker chars are still converted into cells, so the cell type must accept them.
+	pub fn parse_markers<const N: usize>(input: &str, markers: [char; N]) -> anyhow::Result<(Self, [Point<2>; N])> where T: TryFrom<char>, T::Error: Into<anyhow::Error> {
+		let (grid, positions) = Self::parse_inner(input, None, &markers, &mut |ch| T::try_from(ch).map_err(Into::into))?;
+
+		let mut result = [Point::<2>::ZERO; N];
+		for (i, marker) in markers.into_iter().enumerate() {
+			let mut found = positions.iter().filter(|(ch, _)| *ch == marker).map(|(_, at)| *at);
+			result[i] = found.next().with_context(|| format!("Marker {:?} not found", marker))?;
+			anyhow::ensure!(found.next().is_none(), "Marker {:?} found more than once", marker);
+		}
+
+		Ok((grid, result))
+	}
+
+	fn parse_inner(
+		input: &str,
+		fill: Option<&dyn Fn() -> T>,
+		markers: &[char],
+		convert: &mut dyn FnMut(char) -> anyhow::Result<T>
+	) -> anyhow::Result<(Self, Vec<(char, Point<2>)>)> {
+		let lines: Vec<&str> = input.lines().take_while(|line| !line.is_empty()).collect();
+		let width = lines.iter().map(|line| line.chars().count()).max().context("Input contains no lines")?;
+
+		let mut cells = Vec::with_capacity(width * lines.len());
+		let mut positions = Vec::new();
+		for (y, line) in lines.iter().enumerate() {
+			let mut line_width = 0;
+			for (x, ch) in line.chars().enumerate() {
+				let cell = convert(ch).with_context(
+					|| format!("Invalid cell {:?} at line {}, column {}", ch, y + 1, x + 1)
+				)?;
+				cells.push(cell);
+
+				if markers.contains(&ch) {
+					positions.push((ch, Point::<2>::new(x as isize, y as isize)));
+				}
+				line_width += 1;
+			}
+
+			if line_width < width {
+				match fill {
+					None => anyhow::bail!("Line {} has length {} but expected {}", y + 1, line_width, width),
+					Some(fill) => cells.extend(std::iter::repeat_with(fill).take(width - line_width))
+				}
+			}
+		}
+
+		let grid = Self::new(
+			cells,
+			Rectangle { min: Point::<2>::ZERO, max: Point::<2>::new(width as isize, lines.len() as isize) }
+		)?;
+
+		Ok((grid, positions))
 	}
 
 	pub fn width(&self) -> isize {
@@ -489,73 +936,173 @@ impl<T> Grid2<T> {
 	}
 
 	pub fn x_range(&self) -> Range<isize> {
-		self.bounding_box.min.x() .. self.bounding_box.max.x()
+		self.dim_range(0)
 	}
 
 	pub fn y_range(&self) -> Range<isize> {
-		self.bounding_box.min.y() .. self.bounding_box.max.y()
+		self.dim_range(1)
 	}
 
-	pub fn index_to_coords(&self, index: usize) -> Option<Point<2>> {
-		if index >= self.cells.len() {
-			None
-		} else {
-			let width = self.width().abs() as usize;
-			let x = index % width;
-			let y = index / width;
-
-			Some(self.bounding_box.min + Point::<2>::new(x as isize, y as isize))
+	/// Cells of row `y` from the lowest x.
+	pub fn row(&self, y: isize) -> Option<impl DoubleEndedIterator<Item = &T> + ExactSizeIterator> {
+		if !self.y_range().contains(&y) {
+			return None;
 		}
-	}
 
-	pub fn get(&self, at: Point<2>) -> Option<&T> {
-		let at = at - self.bounding_box.min;
-		self.get_relative(at)
+		let width = self.width() as usize;
+		let start = (y - self.bounding_box.min.y()) as usize * width;
+		Some(self.cells[start .. start + width].iter())
 	}
 
-	pub fn get_relative(&self, at: Point<2>) -> Option<&T> {
-		let size = self.bounding_box.size();
-		if at.x() < 0 || at.x() >= size.x() || at.y() < 0 || at.y() >= size.y() {
+	/// Cells of column `x` from the lowest y.
+	pub fn column(&self, x: isize) -> Option<impl DoubleEndedIterator<Item = &T> + ExactSizeIterator> {
+		if !self.x_range().contains(&x) {
 			return None;
 		}
 
-		Some(&self.cells[at.x() as usize + at.y() as usize * size.x().abs() as usize])
+		let start = (x - self.bounding_box.min.x()) as usize;
+		Some(self.cells.iter().skip(start).step_by(self.width() as usize))
 	}
 
-	pub fn get_mut(&mut self, at: Point<2>) -> Option<&mut T> {
-		let at = at - self.bounding_box.min;
-		self.get_mut_relative(at)
+	/// View of the part of the grid inside `area`, clipped to the bounding box of the grid.
+	pub fn view(&self, area: Rectangle<2>) -> SubGrid<'_, T> {
+		SubGrid { grid: self, area: area.intersection(&self.bounding_box) }
 	}
+}
+impl<T: Clone> Grid<T, 2> {
+	/// Creates a grid of `size` with the same min corner, taking each cell from the relative position returned by `source`.
+	fn remap(&self, size: Point<2>, source: impl Fn(isize, isize) -> (isize, isize)) -> Self {
+		let min = self.bounding_box.min;
+		let bounding_box = Rectangle { min, max: min + size };
+		let cells = bounding_box.points_iter().map(|at| {
+			let (x, y) = source(at.x() - min.x(), at.y() - min.y());
+			self.get_relative(Point::<2>::new(x, y)).unwrap().clone()
+		}).collect();
 
-	pub fn get_mut_relative(&mut self, at: Point<2>) -> Option<&mut T> {
+		Self { cells, bounding_box, grow_fill: self.grow_fill }
+	}
+
+	/// Rotates the grid clockwise (with y pointing down). The min corner stays in place, width and height are swapped.
+	pub fn rotate_cw(&self) -> Self {
+		let height = self.height();
+		self.remap(Point::<2>::new(height, self.width()), |x, y| (y, height - 1 - x))
+	}
+
+	/// Rotates the grid counterclockwise (with y pointing down). The min corner stays in place, width and height are swapped.
+	pub fn rotate_ccw(&self) -> Self {
+		let width = self.width();
+		self.remap(Point::<2>::new(self.height(), width), |x, y| (width - 1 - y, x))
+	}
+
+	/// Mirrors the grid along its main diagonal, relative to the min corner.
+	pub fn transpose(&self) -> Self {
+		self.remap(Point::<2>::new(self.height(), self.width()), |x, y| (y, x))
+	}
+
+	/// Mirrors the grid horizontally, reversing each row.
+	pub fn flip_x(&self) -> Self {
+		let width = self.width();
+		self.remap(self.bounding_box.size(), |x, y| (width - 1 - x, y))
+	}
+
+	/// Mirrors the grid vertically, reversing each column.
+	pub fn flip_y(&self) -> Self {
+		let height = self.height();
+		self.remap(self.bounding_box.size(), |x, y| (x, height - 1 - y))
+	}
+}
+/// Displays the grid as a sequence of 2D slices (x by y), each higher-dimension slice is prefixed by its coordinates.
+impl<T: fmt::Display, const D: usize> fmt::Display for Grid<T, D> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		let size = self.bounding_box.size();
-		if at.x() < 0 || at.x() >= size.x() || at.y() < 0 || at.y() >= size.y() {
-			return None;
+		let width = if D > 0 { size.0[0].max(0) as usize } else { 1 };
+		let height = if D > 1 { size.0[1].max(0) as usize } else { 1 };
+		let slice_len = width * height;
+		if slice_len == 0 {
+			return Ok(());
 		}
 
-		Some(&mut self.cells[at.x() as usize + at.y() as usize * size.x().abs() as usize])
-	}
+		for (slice_index, slice) in self.cells.chunks(slice_len).enumerate() {
+			if D > 2 {
+				if slice_index > 0 {
+					writeln!(f)?;
+				}
 
-	pub fn shift(&mut self, shift: Point<2>) {
-		self.bounding_box = self.bounding_box + shift;
+				let at = self.index_to_coords(slice_index * slice_len).unwrap();
+				write!(f, "slice {}", at.0[2])?;
+				for v in &at.0[3 ..] {
+					write!(f, ", {}", v)?;
+				}
+				writeln!(f, ":")?;
+			}
+
+			for row in slice.chunks(width) {
+				for cell in row {
+					write!(f, "{}", cell)?;
+				}
+				writeln!(f)?;
+			}
+		}
+
+		Ok(())
 	}
 }
-impl<T: Copy> Grid2<T> {
-	pub fn new_fill(fill: T, bounding_box: Rectangle<2>) -> anyhow::Result<Self> {
-		Self::new(vec![fill; bounding_box.area()], bounding_box)
+
+/// Borrowed rectangular part of a [`Grid2`] created by [`Grid::view`], coordinates are relative to the min corner of the part.
+#[derive(Debug)]
+pub struct SubGrid<'a, T> {
+	grid: &'a Grid2<T>,
+	/// Absolute area in the grid, always inside of its bounding box.
+	area: Rectangle<2>
+}
+impl<'a, T> SubGrid<'a, T> {
+	/// Absolute area of the view in the underlying grid.
+	pub fn area(&self) -> Rectangle<2> {
+		self.area
 	}
 
-	pub fn fill(&mut self, fill: T) {
-		for cell in self.cells.iter_mut() {
-			*cell = fill;
+	/// Relative bounding box, starting at zero.
+	pub fn bounding_box(&self) -> Rectangle<2> {
+		Rectangle { min: Point::<2>::ZERO, max: self.area.size() }
+	}
+
+	pub fn width(&self) -> isize {
+		self.area.size().x()
+	}
+
+	pub fn height(&self) -> isize {
+		self.area.size().y()
+	}
+
+	pub fn get(&self, at: Point<2>) -> Option<&'a T> {
+		if !self.bounding_box().contains(&at) {
+			return None;
 		}
+
+		self.grid.get(self.area.min + at)
+	}
+
+	/// Iterates relative positions of all cells together with the cells, rows from the lowest y.
+	pub fn iter(&self) -> impl Iterator<Item = (Point<2>, &'a T)> + '_ {
+		self.bounding_box().points_iter().map(|at| (at, self.get(at).unwrap()))
+	}
+
+	/// Copies the view into a new grid with the same relative coordinates.
+	pub fn to_grid(&self) -> Grid2<T> where T: Clone {
+		Grid::new(self.iter().map(|(_, cell)| cell.clone()).collect(), self.bounding_box()).unwrap()
+	}
+}
+impl<T> Clone for SubGrid<'_, T> {
+	fn clone(&self) -> Self {
+		*self
 	}
 }
-impl<T: fmt::Display> fmt::Display for Grid2<T> {
+impl<T> Copy for SubGrid<'_, T> {}
+impl<T: fmt::Display> fmt::Display for SubGrid<'_, T> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		for y in 0 .. self.height() {
 			for x in 0 .. self.width() {
-				write!(f, "{}", self.get_relative(Point::<2>::new(x, y)).unwrap())?;
+				write!(f, "{}", self.get(Point::<2>::new(x, y)).unwrap())?;
 			}
 			writeln!(f)?;
 		}
@@ -564,12 +1111,156 @@ impl<T: fmt::Display> fmt::Display for Grid2<T> {
 	}
 }
 
+/// Unbounded grid which only stores cells that were set, all other cells have the default value.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T, const D: usize> {
+	cells: HashMap<Point<D>, T>,
+	default: T,
+	bounding_box: Rectangle<D>,
+	shrink_on_remove: bool
+}
+pub type SparseGrid2<T> = SparseGrid<T, 2>;
+pub type SparseGrid3<T> = SparseGrid<T, 3>;
+impl<T, const D: usize> SparseGrid<T, D> {
+	pub fn new(default: T) -> Self {
+		Self { cells: HashMap::new(), default, bounding_box: Rectangle::EMPTY, shrink_on_remove: false }
+	}
+
+	/// Whether [`SparseGrid::remove`] recomputes the bounding box when a cell on its edge is removed.
+	///
+	/// Recomputing visits all cells, so this is off by default and the bounding box only ever grows.
+	pub fn set_shrink_on_remove(&mut self, shrink: bool) {
+		self.shrink_on_remove = shrink;
+	}
+
+	pub fn default_value(&self) -> &T {
+		&self.default
+	}
+
+	/// Rectangle containing all set cells, it may be larger than needed if cells were removed.
+	pub fn bounding_box(&self) -> Rectangle<D> {
+		self.bounding_box
+	}
+
+	/// Recomputes the bounding box to be the smallest one containing all set cells.
+	pub fn shrink_bounding_box(&mut self) {
+		self.bounding_box = Rectangle::from_points(self.cells.keys().copied());
+	}
+
+	/// Number of set cells.
+	pub fn len(&self) -> usize {
+		self.cells.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.cells.is_empty()
+	}
+
+	/// Whether the cell at `at` is set, even if to the default value.
+	pub fn contains(&self, at: Point<D>) -> bool {
+		self.cells.contains_key(&at)
+	}
+
+	pub fn get(&self, at: Point<D>) -> &T {
+		self.cells.get(&at).unwrap_or(&self.default)
+	}
+
+	pub fn insert(&mut self, at: Point<D>, value: T) -> Option<T> {
+		self.grow(at);
+		self.cells.insert(at, value)
+	}
+
+	/// Unsets the cell at `at`, so that it has the default value again.
+	pub fn remove(&mut self, at: Point<D>) -> Option<T> {
+		let removed = self.cells.remove(&at);
+
+		let on_edge = (0 .. D).any(|i| at.0[i] == self.bounding_box.min.0[i] || at.0[i] == self.bounding_box.max.0[i] - 1);
+		if removed.is_some() && self.shrink_on_remove && on_edge {
+			self.shrink_bounding_box();
+		}
+
+		removed
+	}
+
+	/// Iterates the set cells in arbitrary order.
+	pub fn iter(&self) -> impl Iterator<Item = (Point<D>, &T)> {
+		self.cells.iter().map(|(at, cell)| (*at, cell))
+	}
+
+	fn grow(&mut self, at: Point<D>) {
+		self.bounding_box = self.bounding_box.bounding_union(&Rectangle { min: at, max: at + Point([1; D]) });
+	}
+}
+impl<T: Clone, const D: usize> SparseGrid<T, D> {
+	/// Like [`SparseGrid::get`], but a cell which is not set is first set to the default value.
+	pub fn get_mut(&mut self, at: Point<D>) -> &mut T {
+		self.grow(at);
+		self.cells.entry(at).or_insert_with(|| self.default.clone())
+	}
+
+	/// Sets all cells of `grid` which are not equal to `default`.
+	pub fn from_grid(grid: &Grid<T, D>, default: T) -> Self where T: PartialEq {
+		let mut me = Self::new(default);
+		for at in grid.bounding_box().points_iter() {
+			let cell = grid.get(at).unwrap();
+			if *cell != me.default {
+				me.insert(at, cell.clone());
+			}
+		}
+
+		me
+	}
+
+	/// Dense grid covering the bounding box.
+	pub fn to_grid(&self) -> Grid<T, D> {
+		let cells = self.bounding_box.points_iter().map(|at| self.get(at).clone()).collect();
+
+		Grid::new(cells, self.bounding_box).expect("points_iter visits every point of the bounding box")
+	}
+}
+impl<T> SparseGrid<T, 2> {
+	fn fmt_with(&self, f: &mut fmt::Formatter<'_>, empty: Option<&str>) -> fmt::Result where T: fmt::Display {
+		for y in self.bounding_box.min.y() .. self.bounding_box.max.y() {
+			for x in self.bounding_box.min.x() .. self.bounding_box.max.x() {
+				match (self.cells.get(&Point::<2>::new(x, y)), empty) {
+					(Some(cell), _) => write!(f, "{}", cell)?,
+					(None, Some(empty)) => write!(f, "{}", empty)?,
+					(None, None) => write!(f, "{}", self.default)?
+				}
+			}
+			writeln!(f)?;
+		}
+
+		Ok(())
+	}
+
+	/// Like the [`fmt::Display`] output, but cells which are not set are rendered as `empty` instead of the default value.
+	pub fn render(&self, empty: &str) -> String where T: fmt::Display {
+		struct Render<'a, T>(&'a SparseGrid<T, 2>, &'a str);
+		impl<T: fmt::Display> fmt::Display for Render<'_, T> {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				self.0.fmt_with(f, Some(self.1))
+			}
+		}
+
+		Render(self, empty).to_string()
+	}
+}
+/// Displays the bounding box row by row, cells which are not set show the default value.
+impl<T: fmt::Display> fmt::Display for SparseGrid<T, 2> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.fmt_with(f, None)
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use std::collections::HashSet;
-	use super::{Point, Rectangle, Circle2};
+	use anyhow::Context;
+	use super::{Point, Rectangle, Rotation3, Circle2, Grid, Grid2, SparseGrid, SparseGrid2};
 
 	type Point2 = Point::<2>;
+	type Point3 = Point::<3>;
 
 	#[test]
 	fn test_rectangle_intersection() {
@@ -581,11 +1272,12 @@ mod test {
 		// self
 		assert_eq!(a.intersection(&a), a);
 		// empty
-		assert_eq!(d.intersection(&a), Rectangle { min: Point2::new(0, 0), max: Point2::new(-1, -1) });
+		assert_eq!(d.intersection(&a), Rectangle::EMPTY);
 		// empty self
-		assert_eq!(d.intersection(&d), d);
+		assert_eq!(d.intersection(&d), Rectangle::EMPTY);
 		// no intersection
-		assert_eq!(b.intersection(&a), Rectangle { min: Point2::new(0, 2), max: Point2::new(0, 2) });
+		assert_eq!(b.intersection(&a), Rectangle::EMPTY);
+		assert!(b.intersection(&a).is_empty());
 		// negative coordinates
 		assert_eq!(c.intersection(&a), Rectangle { min: Point2::new(0, 0), max: Point2::new(1, 1) });
 	}
@@ -599,6 +1291,169 @@ mod test {
 		assert_eq!(Point2::new(2, 1).rot2d_clockwise(), Point2::new(3, 0));
 	}
 
+	#[test]
+	fn test_rectangle_union() {
+		let a = Rectangle { min: Point2::new(0, 0), max: Point2::new(2, 2) };
+		let b = Rectangle { min: Point2::new(-2, 3), max: Point2::new(0, 4) };
+		let empty = Rectangle { min: Point2::new(10, 10), max: Point2::new(10, 20) };
+
+		assert!(empty.is_empty());
+		assert!(!a.is_empty());
+		assert_eq!(a.bounding_union(&b), Rectangle { min: Point2::new(-2, 0), max: Point2::new(2, 4) });
+		assert_eq!(a.bounding_union(&empty), a);
+		assert_eq!(empty.bounding_union(&b), b);
+		assert_eq!(empty.bounding_union(&empty), Rectangle::EMPTY);
+
+		assert_eq!(
+			Rectangle::from_points([Point2::new(3, -1), Point2::new(0, 2), Point2::new(1, 1)]),
+			Rectangle { min: Point2::new(0, -1), max: Point2::new(4, 3) }
+		);
+		assert_eq!(Rectangle::from_points([Point3::new(1, 2, 3)]).area(), 1);
+		assert_eq!(Rectangle::<2>::from_points([]), Rectangle::EMPTY);
+	}
+
+	#[test]
+	fn test_rectangle_subtract() {
+		let a = Rectangle { min: Point3::new(0, 0, 0), max: Point3::new(4, 4, 4) };
+
+		// hole in the middle
+		let hole = Rectangle { min: Point3::new(1, 1, 1), max: Point3::new(3, 3, 3) };
+		let parts = a.subtract(&hole);
+		assert_eq!(parts.len(), 6);
+		assert_eq!(parts.iter().map(|r| r.area()).sum::<usize>(), 64 - 8);
+		for (i, p) in parts.iter().enumerate() {
+			assert!(p.intersection(&hole).is_empty());
+			assert!(parts[i + 1 ..].iter().all(|q| p.intersection(q).is_empty()));
+		}
+
+		// corner
+		let corner = Rectangle { min: Point3::new(2, 2, 2), max: Point3::new(10, 10, 10) };
+		let parts = a.subtract(&corner);
+		assert_eq!(parts.len(), 3);
+		assert_eq!(parts.iter().map(|r| r.area()).sum::<usize>(), 64 - 8);
+
+		// disjoint, covering and empty
+		let far = Rectangle { min: Point3::new(5, 0, 0), max: Point3::new(6, 1, 1) };
+		assert_eq!(a.subtract(&far), vec![a]);
+		assert_eq!(hole.subtract(&a), vec![]);
+		assert_eq!(Rectangle::EMPTY.subtract(&a), vec![]);
+	}
+
+	#[test]
+	fn test_points_iter() {
+		let r = Rectangle { min: Point2::new(-1, 2), max: Point2::new(1, 4) };
+		assert_eq!(
+			r.points_iter().collect::<Vec<_>>(),
+			vec![Point2::new(-1, 2), Point2::new(0, 2), Point2::new(-1, 3), Point2::new(0, 3)]
+		);
+
+		let r = Rectangle { min: Point3::new(0, 0, 0), max: Point3::new(2, 1, 2) };
+		assert_eq!(r.points_iter().nth(2), Some(Point3::new(0, 0, 1)));
+
+		let r = Rectangle { min: Point::<4>::ZERO, max: Point::from([2, 3, 1, 2]) };
+		let points: HashSet<Point<4>> = r.points_iter().collect();
+		assert_eq!(points.len(), r.area());
+		assert!(points.iter().all(|p| r.contains(p)));
+
+		let empty = Rectangle { min: Point2::new(0, 0), max: Point2::new(3, 0) };
+		assert_eq!(empty.points_iter().count(), 0);
+	}
+
+	#[test]
+	fn test_vector_ops() {
+		let a = Point3::new(1, 2, 3);
+		let b = Point3::new(-4, 0, 2);
+		assert_eq!(a.dot(b), 2);
+		assert_eq!(a.cross(b), Point3::new(4, -14, 8));
+		assert_eq!(a.cross(b).dot(a), 0);
+		assert_eq!(b.cross(a), -a.cross(b));
+
+		assert_eq!(b.length_chebyshev(), 4);
+		assert_eq!(b.length_squared(), 20);
+		assert_eq!(Point2::new(-3, 1).length_chebyshev(), 3);
+		assert_eq!(Point2::new(2, 1).perp_dot(Point2::new(1, 3)), 5);
+	}
+
+	#[test]
+	fn test_scalar_types() {
+		let a = Point::<2, u8>::new(200, 3);
+		let b = Point::<2, u8>::new(100, 4);
+		assert_eq!(a.checked_add(b), None);
+		assert_eq!(a.checked_sub(b), None);
+		assert_eq!(b.checked_sub(Point::<2, _>::new(1, 1)), Some(Point::<2, _>::new(99, 3)));
+		assert_eq!(a.saturating_add(b), Point::<2, _>::new(255, 7));
+		assert_eq!(a.saturating_sub(b), Point::<2, _>::new(100, 0));
+		assert_eq!(a.checked_mul(2), None);
+		assert_eq!(a.length_manhattan(), 203);
+
+		let wide: Point<2, i64> = a.cast();
+		assert_eq!(wide * 2, Point::<2, _>::new(400, 6));
+		assert_eq!(wide.try_cast::<i8>(), None);
+		assert_eq!(Point2::new(-1, 5).try_cast::<u8>(), None);
+		assert_eq!(Point2::new(1, 5).try_cast::<u8>(), Some(Point::<2, _>::new(1, 5)));
+
+		let big = Point::<2, i128>::new(4_000_000, 3);
+		assert_eq!(big.x() * 4_000_000_000_000 + big.y(), 16_000_000_000_000_000_003);
+		assert_eq!(<[i128; 2]>::from(big), [4_000_000, 3]);
+		assert_eq!(Point::from([1, 2, 3]), Point3::new(1, 2, 3));
+
+		let r = Rectangle { min: Point::<2, u8>::new(1, 0), max: Point::<2, _>::new(3, 2) };
+		assert_eq!(r.area(), 4);
+		assert_eq!(r.points_iter().last(), Some(Point::<2, _>::new(2, 1)));
+		assert_eq!(r.wrap_around(Point::<2, _>::new(6, 7)), Point::<2, _>::new(2, 1));
+
+		let mut p = Point::<3, i32>::ZERO;
+		p += Point::<3, _>::new(1, 2, 3);
+		p -= Point::<3, _>::new(0, 0, 1);
+		assert_eq!(p, Point::<3, _>::new(1, 2, 2));
+		assert_eq!(Point::<3, i16>::MAX.length_chebyshev(), i16::MAX);
+	}
+
+	#[test]
+	fn test_rot90() {
+		let p = Point2::new(3, 1);
+		assert_eq!(p.rot90(1), Point2::new(-1, 3));
+		assert_eq!(p.rot90(2), Point2::new(-3, -1));
+		assert_eq!(p.rot90(-1), Point2::new(1, -3));
+		assert_eq!(p.rot90(4), p);
+		assert_eq!(p.rot90(7), p.rot90(-1));
+		assert_eq!(p.rot90(1).rot90(1), p.rot90(2));
+
+		// with y down, a positive turn from up faces right
+		assert_eq!(Point2::new(0, -1).rot90(1), Point2::new(1, 0));
+	}
+
+	#[test]
+	fn test_rotation3() {
+		let all = Rotation3::all();
+		assert_eq!(all[0], Rotation3::IDENTITY);
+		assert_eq!(all.iter().collect::<HashSet<_>>().len(), 24);
+
+		for a in all.iter() {
+			assert_eq!(a.determinant(), 1);
+			assert_eq!(a.compose(&a.inverse()), Rotation3::IDENTITY);
+			assert_eq!(a.inverse().compose(a), Rotation3::IDENTITY);
+			for b in all.iter() {
+				assert!(all.contains(&a.compose(b)));
+			}
+		}
+
+		let p = Point3::new(1, 2, 3);
+		assert_eq!(Rotation3::X.apply(p), Point3::new(1, -3, 2));
+		assert_eq!(Rotation3::Y.apply(p), Point3::new(3, 2, -1));
+		assert_eq!(Rotation3::Z.apply(p), Point3::new(-2, 1, 3));
+		assert_eq!(Rotation3::X.compose(&Rotation3::Z).apply(p), Rotation3::X.apply(Rotation3::Z.apply(p)));
+
+		let quarter = |r: Rotation3| (1 ..= 4).map(|n| (0 .. n).fold(Rotation3::IDENTITY, |acc, _| acc.compose(&r))).position(|r| r == Rotation3::IDENTITY);
+		assert_eq!(quarter(Rotation3::X), Some(3));
+		assert_eq!(quarter(Rotation3::Y), Some(3));
+		assert_eq!(quarter(Rotation3::Z), Some(3));
+
+		// distinct images of a point with distinct coordinates identify the rotation
+		let images: HashSet<Point3> = all.iter().map(|r| r.apply(p)).collect();
+		assert_eq!(images.len(), 24);
+	}
+
 	#[test]
 	fn test_disk_intersection() {
 		/*
@@ -629,4 +1484,316 @@ mod test {
 		assert_eq!(disk_intersection(&b, &c), &[Point2::new(8, 5), Point2::new(7, 6)]);
 		assert_eq!(disk_intersection(&c, &d), &[Point2::new(8, 5), Point2::new(7, 6), Point2::new(8, 7)]);
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn test_diamond_analytic() {
+		let circles = [
+			Circle2::new_radius(Point2::new(2, 3), 2),
+			Circle2::new_radius(Point2::new(7, 3), 3),
+			Circle2::new_radius(Point2::new(-1, 0), 0),
+			Circle2::new_radius(Point2::new(5, 6), 2)
+		];
+		let area = Rectangle { min: Point2::new(-5, -5), max: Point2::new(15, 15) };
+
+		for c in circles.iter() {
+			for y in area.min.y() .. area.max.y() {
+				let expected: Vec<isize> = (area.min.x() .. area.max.x()).filter(|&x| c.contains_manhattan(Point2::new(x, y))).collect();
+				assert_eq!(c.row_range(y).map(|r| r.collect::<Vec<_>>()).unwrap_or_default(), expected);
+			}
+			assert_eq!(c.column_range(c.center().x() + 1), c.row_range(c.center().y() - 1).map(|r| r.start - c.center().x() + c.center().y() .. r.end - c.center().x() + c.center().y()));
+
+			let inside: Vec<Point2> = area.points_iter().filter(|p| c.contains_manhattan(*p)).collect();
+			assert_eq!(Rectangle::from_points(inside.iter().copied()), c.bounding_box());
+			assert!(c.inner_bounding_box().points_iter().all(|p| c.contains_manhattan(p)));
+			assert_eq!(c.inner_bounding_box().size().x(), c.radius() / 2 * 2 + 1);
+
+			let diagonal = c.diagonal_box();
+			for p in area.points_iter() {
+				assert_eq!(diagonal.contains(&p.to_diagonal()), c.contains_manhattan(p));
+				assert_eq!(p.to_diagonal().from_diagonal(), Some(p));
+			}
+		}
+		assert_eq!(Point2::new(1, 2).from_diagonal(), None);
+
+		for a in circles.iter() {
+			for b in circles.iter() {
+				let mut crossings = a.boundary_crossings(b);
+				crossings.sort_by_key(|p| (p.x(), p.y()));
+				assert!(crossings.iter().all(|p| a.on_boundary(*p) && b.on_boundary(*p)));
+
+				let mut reverse = b.boundary_crossings(a);
+				reverse.sort_by_key(|p| (p.x(), p.y()));
+				assert_eq!(crossings, reverse);
+			}
+		}
+		assert_eq!(circles[0].boundary_crossings(&circles[1]), vec![Point2::new(4, 3)]);
+		let mut crossings = circles[1].boundary_crossings(&circles[3]);
+		crossings.sort_by_key(|p| (p.x(), p.y()));
+		// the edges overlap from (5, 4) to (7, 6), points in between are not crossings
+		assert_eq!(crossings, vec![Point2::new(5, 4), Point2::new(7, 6)]);
+		assert_eq!(circles[0].boundary_crossings(&circles[2]), vec![]);
+	}
+
+	#[test]
+	fn test_grid_index() {
+		let bounding_box = Rectangle { min: Point3::new(-1, 0, 2), max: Point3::new(2, 2, 4) };
+		let grid = Grid::new((0 .. 12).collect::<Vec<usize>>(), bounding_box).unwrap();
+
+		assert_eq!(grid.get(Point3::new(-1, 0, 2)), Some(&0));
+		assert_eq!(grid.get(Point3::new(1, 0, 2)), Some(&2));
+		assert_eq!(grid.get(Point3::new(-1, 1, 2)), Some(&3));
+		assert_eq!(grid.get(Point3::new(0, 1, 3)), Some(&10));
+		assert_eq!(grid.get(Point3::new(2, 0, 2)), None);
+		assert_eq!(grid.get(Point3::new(0, 0, 4)), None);
+
+		for index in 0 .. 12 {
+			let at = grid.index_to_coords(index).unwrap();
+			assert_eq!(grid.get(at), Some(&index));
+		}
+		assert_eq!(grid.index_to_coords(12), None);
+	}
+
+	#[test]
+	fn test_grid_display() {
+		let grid = Grid2::new_width(vec![1, 2, 3, 4, 5, 6], 3).unwrap();
+		assert_eq!(grid.to_string(), "123\n456\n");
+
+		let mut grid = Grid::new_fill('.', Rectangle { min: Point3::new(0, 0, 0), max: Point3::new(2, 1, 2) }).unwrap();
+		*grid.get_mut(Point3::new(1, 0, 1)).unwrap() = '#';
+		grid.shift(Point3::new(0, 0, 5));
+		assert_eq!(grid.to_string(), "slice 5:\n..\n\nslice 6:\n.#\n");
+	}
+
+	#[test]
+	fn test_grid_parse() {
+		#[derive(Debug, Clone, Copy, PartialEq)]
+		enum Cell { Void, Ground, Rock }
+		impl TryFrom<char> for Cell {
+			type Error = anyhow::Error;
+
+			fn try_from(value: char) -> Result<Self, Self::Error> {
+				match value {
+					' ' => Ok(Self::Void),
+					'.' | 'S' | 'E' => Ok(Self::Ground),
+					'#' => Ok(Self::Rock),
+					_ => anyhow::bail!("Invalid cell char")
+				}
+			}
+		}
+
+		let grid = Grid2::<Cell>::parse("..#\n#..\n\n10R5").unwrap();
+		assert_eq!(grid.bounding_box(), Rectangle { min: Point2::new(0, 0), max: Point2::new(3, 2) });
+		assert_eq!(grid.get(Point2::new(2, 0)), Some(&Cell::Rock));
+		assert_eq!(grid.get(Point2::new(1, 1)), Some(&Cell::Ground));
+
+		let err = Grid2::<Cell>::parse("..#\n#.x\n").unwrap_err();
+		assert_eq!(err.to_string(), "Invalid cell 'x' at line 2, column 3");
+		assert!(Grid2::<Cell>::parse("  ..\n.\n").is_err());
+
+		let grid = Grid2::parse_padded("  ..\n.\n", Cell::Void).unwrap();
+		assert_eq!(grid.get(Point2::new(0, 0)), Some(&Cell::Void));
+		assert_eq!(grid.get(Point2::new(0, 1)), Some(&Cell::Ground));
+		assert_eq!(grid.get(Point2::new(3, 1)), Some(&Cell::Void));
+
+		let (grid, [start, end]) = Grid2::<Cell>::parse_markers("S.#\n#.E\n", ['S', 'E']).unwrap();
+		assert_eq!(start, Point2::new(0, 0));
+		assert_eq!(end, Point2::new(2, 1));
+		assert_eq!(grid.get(end), Some(&Cell::Ground));
+
+		assert!(Grid2::<Cell>::parse_markers("S.#\n#.S\n", ['S', 'E']).is_err());
+
+		let grid = Grid2::parse_with("12\n34\n", |ch| ch.to_digit(10).context("Not a digit")).unwrap();
+		assert_eq!(grid.get(Point2::new(1, 1)), Some(&4));
+	}
+
+	#[test]
+	fn test_neighbors() {
+		assert_eq!(
+			Point2::new(0, 0).neighbors_orthogonal().collect::<Vec<_>>(),
+			vec![Point2::new(-1, 0), Point2::new(1, 0), Point2::new(0, -1), Point2::new(0, 1)]
+		);
+		assert_eq!(Point3::new(0, 0, 0).neighbors_orthogonal().count(), 6);
+
+		let all: HashSet<Point2> = Point2::new(5, 5).neighbors_all().collect();
+		assert_eq!(all.len(), 8);
+		assert!(!all.contains(&Point2::new(5, 5)));
+		assert!(all.iter().all(|p| (*p - Point2::new(5, 5)).length_manhattan() <= 2));
+
+		let all: HashSet<Point3> = Point3::new(0, 0, 0).neighbors_all().collect();
+		assert_eq!(all.len(), 26);
+		assert!(!all.contains(&Point3::ZERO));
+	}
+
+	#[test]
+	fn test_grid_neighbors() {
+		let grid = Grid2::new_width((0 .. 12).collect::<Vec<usize>>(), 4).unwrap();
+
+		assert_eq!(
+			grid.neighbors_orthogonal(Point2::new(0, 0)).collect::<Vec<_>>(),
+			vec![(Point2::new(1, 0), &1), (Point2::new(0, 1), &4)]
+		);
+		assert_eq!(grid.neighbors_all(Point2::new(3, 2)).count(), 3);
+		assert_eq!(grid.neighbors_all(Point2::new(1, 1)).count(), 8);
+
+		assert_eq!(
+			grid.neighbors_orthogonal_wrapping(Point2::new(0, 0)).collect::<Vec<_>>(),
+			vec![(Point2::new(3, 0), &3), (Point2::new(1, 0), &1), (Point2::new(0, 2), &8), (Point2::new(0, 1), &4)]
+		);
+		assert_eq!(grid.neighbors_all_wrapping(Point2::new(0, 0)).count(), 8);
+		assert_eq!(grid.get_wrapping(Point2::new(-5, 7)), Some(&7));
+	}
+
+	#[test]
+	fn test_grid_growing() {
+		let mut grid = Grid2::new_width((0 .. 6).collect::<Vec<usize>>(), 3).unwrap();
+
+		grid.ensure_contains(Point2::new(1, 1), 0);
+		assert_eq!(grid.bounding_box(), Rectangle { min: Point2::new(0, 0), max: Point2::new(3, 2) });
+
+		grid.ensure_contains(Point2::new(-1, 2), 9);
+		assert_eq!(grid.bounding_box(), Rectangle { min: Point2::new(-3, 0), max: Point2::new(3, 4) });
+		let original = Rectangle { min: Point2::new(0, 0), max: Point2::new(3, 2) };
+		for (index, at) in original.points_iter().enumerate() {
+			assert_eq!(grid.get(at), Some(&index));
+		}
+		assert_eq!(grid.get(Point2::new(-3, 3)), Some(&9));
+		assert_eq!(grid.get(Point2::new(3, 0)), None);
+		for index in 0 .. grid.bounding_box().area() {
+			let at = grid.index_to_coords(index).unwrap();
+			assert!(grid.get(at).is_some());
+		}
+
+		// far away points are included even if beyond the headroom
+		grid.ensure_contains(Point2::new(100, 0), 9);
+		assert_eq!(grid.bounding_box().max, Point2::new(101, 4));
+		assert_eq!(grid.get(Point2::new(2, 1)), Some(&5));
+
+		let mut grid = Grid::new_fill('.', Rectangle::<3>::EMPTY).unwrap();
+		assert_eq!(grid.get_mut(Point3::new(5, 5, 5)), None);
+		grid.set_growing(Some(|| '.'));
+		*grid.get_mut(Point3::new(5, 5, 5)).unwrap() = '#';
+		*grid.get_mut(Point3::new(4, 5, 5)).unwrap() = '#';
+		assert_eq!(grid.get(Point3::new(5, 5, 5)), Some(&'#'));
+		assert_eq!(grid.get(Point3::new(4, 5, 5)), Some(&'#'));
+		assert_eq!(grid.get(Point3::new(3, 5, 5)), Some(&'.'));
+
+		// growing one cell at a time reallocates only logarithmically often
+		let mut grid = Grid2::new_fill(0u8, Rectangle { min: Point2::ZERO, max: Point2::new(1, 1) }).unwrap();
+		grid.set_growing(Some(|| 0));
+		let mut reallocations = 0;
+		for x in 0 .. 1000 {
+			let before = grid.bounding_box();
+			*grid.get_mut(Point2::new(-x, 0)).unwrap() = 1;
+			reallocations += (before != grid.bounding_box()) as usize;
+		}
+		assert!(reallocations <= 10);
+	}
+
+	#[test]
+	fn test_grid_transform() {
+		let mut grid = Grid2::<char>::parse("123\n456\n").unwrap();
+		grid.shift(Point2::new(-1, 5));
+
+		assert_eq!(grid.rotate_cw().to_string(), "41\n52\n63\n");
+		assert_eq!(grid.rotate_ccw().to_string(), "36\n25\n14\n");
+		assert_eq!(grid.transpose().to_string(), "14\n25\n36\n");
+		assert_eq!(grid.flip_x().to_string(), "321\n654\n");
+		assert_eq!(grid.flip_y().to_string(), "456\n123\n");
+
+		let rotated = grid.rotate_cw();
+		assert_eq!(rotated.bounding_box(), Rectangle { min: Point2::new(-1, 5), max: Point2::new(1, 8) });
+		assert_eq!(rotated.get(Point2::new(0, 5)), Some(&'1'));
+		assert_eq!(rotated.rotate_cw().rotate_cw().rotate_cw().to_string(), grid.to_string());
+		assert_eq!(grid.rotate_cw().rotate_ccw().to_string(), grid.to_string());
+		assert_eq!(grid.flip_x().flip_y().to_string(), grid.rotate_cw().rotate_cw().to_string());
+
+		assert_eq!(grid.row(6).unwrap().collect::<String>(), "456");
+		assert_eq!(grid.row(5).unwrap().rev().collect::<String>(), "321");
+		assert_eq!(grid.column(1).unwrap().collect::<String>(), "36");
+		assert_eq!(grid.column(-1).unwrap().rev().collect::<String>(), "41");
+		assert_eq!(grid.column(0).unwrap().len(), 2);
+		assert!(grid.row(7).is_none());
+		assert!(grid.column(2).is_none());
+	}
+
+	#[test]
+	fn test_sub_grid() {
+		let grid = Grid2::new_width((0 .. 16).collect::<Vec<usize>>(), 4).unwrap();
+
+		let view = grid.view(Rectangle { min: Point2::new(1, 1), max: Point2::new(3, 4) });
+		assert_eq!((view.width(), view.height()), (2, 3));
+		assert_eq!(view.get(Point2::new(0, 0)), Some(&5));
+		assert_eq!(view.get(Point2::new(1, 2)), Some(&14));
+		assert_eq!(view.get(Point2::new(2, 0)), None);
+		assert_eq!(view.get(Point2::new(-1, 0)), None);
+		assert_eq!(view.iter().map(|(_, &v)| v).collect::<Vec<_>>(), vec![5, 6, 9, 10, 13, 14]);
+
+		let copy = view.to_grid();
+		assert_eq!(copy.bounding_box(), view.bounding_box());
+		assert_eq!(copy.get(Point2::new(1, 1)), Some(&10));
+
+		// clipped to the grid
+		let view = grid.view(Rectangle { min: Point2::new(2, -5), max: Point2::new(10, 1) });
+		assert_eq!(view.area(), Rectangle { min: Point2::new(2, 0), max: Point2::new(4, 1) });
+		assert_eq!(view.get(Point2::new(0, 0)), Some(&2));
+
+		let view = grid.view(Rectangle { min: Point2::new(5, 5), max: Point2::new(6, 6) });
+		assert_eq!(view.iter().count(), 0);
+		assert_eq!(view.to_string(), "");
+
+		let chars = Grid2::<char>::parse("abc\ndef\nghi\n").unwrap();
+		assert_eq!(chars.view(Rectangle { min: Point2::new(1, 0), max: Point2::new(3, 2) }).to_string(), "bc\nef\n");
+	}
+
+	#[test]
+	fn test_sparse_grid() {
+		let mut grid = SparseGrid2::new('.');
+		assert!(grid.is_empty());
+		assert_eq!(grid.bounding_box(), Rectangle::EMPTY);
+		assert_eq!(grid.get(Point2::new(100, -100)), &'.');
+
+		grid.insert(Point2::new(1, 1), '#');
+		grid.insert(Point2::new(-1, 2), '#');
+		*grid.get_mut(Point2::new(0, 0)) = 'o';
+		assert_eq!(grid.len(), 3);
+		assert_eq!(grid.bounding_box(), Rectangle { min: Point2::new(-1, 0), max: Point2::new(2, 3) });
+		assert_eq!(grid.to_string(), ".o.\n..#\n#..\n");
+		assert_eq!(grid.render(" "), " o \n  #\n#  \n");
+
+		// bounding box only grows by default
+		assert_eq!(grid.remove(Point2::new(-1, 2)), Some('#'));
+		assert_eq!(grid.remove(Point2::new(-1, 2)), None);
+		assert_eq!(grid.bounding_box(), Rectangle { min: Point2::new(-1, 0), max: Point2::new(2, 3) });
+		grid.shrink_bounding_box();
+		assert_eq!(grid.bounding_box(), Rectangle { min: Point2::new(0, 0), max: Point2::new(2, 2) });
+
+		grid.set_shrink_on_remove(true);
+		grid.remove(Point2::new(1, 1));
+		assert_eq!(grid.bounding_box(), Rectangle { min: Point2::new(0, 0), max: Point2::new(1, 1) });
+		assert!(grid.contains(Point2::new(0, 0)));
+		assert!(!grid.contains(Point2::new(1, 1)));
+
+		let dense = Grid2::new_width("#..#.#".chars().collect(), 3).unwrap();
+		let sparse = SparseGrid::from_grid(&dense, '.');
+		assert_eq!(sparse.len(), 3);
+		assert_eq!(sparse.bounding_box(), dense.bounding_box());
+		assert_eq!(sparse.to_grid().to_string(), dense.to_string());
+
+		let mut cube = SparseGrid::<bool, 3>::new(false);
+		cube.insert(Point3::new(0, 0, -5), true);
+		cube.insert(Point3::new(2, 1, 0), true);
+		assert_eq!(cube.to_grid().bounding_box().area(), 3 * 2 * 6);
+		assert_eq!(cube.iter().filter(|(_, v)| **v).count(), 2);
+	}
+
+	#[test]
+	fn test_wrap_around() {
+		let r = Rectangle { min: Point2::new(1, 1), max: Point2::new(4, 3) };
+
+		assert_eq!(r.wrap_around(Point2::new(2, 2)), Point2::new(2, 2));
+		assert_eq!(r.wrap_around(Point2::new(0, 1)), Point2::new(3, 1));
+		assert_eq!(r.wrap_around(Point2::new(4, 3)), Point2::new(1, 1));
+		assert_eq!(r.wrap_around(Point2::new(-6, 10)), Point2::new(3, 2));
+		assert_eq!(r.step_wrapping(Point2::new(3, 2), Point2::new(1, 0)), Point2::new(1, 2));
+	}
+}
@@ -0,0 +1,429 @@
+use std::{
+	collections::BTreeMap,
+	fs::File,
+	io::{self, BufWriter, Write},
+	path::{Path, PathBuf},
+	str::FromStr,
+	sync::Mutex
+};
+
+use anyhow::Context;
+
+use crate::geometry::{Grid2, Point};
+
+pub type Rgb = [u8; 3];
+
+/// Colour of a grid cell when rendering it as an image.
+pub trait Palette {
+	fn color(&self) -> Rgb;
+}
+/// Lit pixels are white, unlit are black.
+impl Palette for bool {
+	fn color(&self) -> Rgb {
+		if *self { [255; 3] } else { [0; 3] }
+	}
+}
+
+/// Rec. 601 luma of the colour.
+pub fn luma([r, g, b]: Rgb) -> u8 {
+	((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageFormat {
+	#[default]
+	Png,
+	/// Binary RGB netpbm.
+	Ppm,
+	/// Binary grayscale netpbm.
+	Pgm
+}
+impl ImageFormat {
+	pub fn extension(&self) -> &'static str {
+		match self {
+			Self::Png => "png",
+			Self::Ppm => "ppm",
+			Self::Pgm => "pgm"
+		}
+	}
+}
+impl FromStr for ImageFormat {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"png" => Ok(Self::Png),
+			"ppm" => Ok(Self::Ppm),
+			"pgm" => Ok(Self::Pgm),
+			v => anyhow::bail!("Invalid image format: {}", v)
+		}
+	}
+}
+
+/// RGB image stored row by row from the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+	width: usize,
+	height: usize,
+	pixels: Vec<Rgb>
+}
+impl Image {
+	pub fn new(width: usize, height: usize, fill: Rgb) -> Self {
+		Self { width, height, pixels: vec![fill; width * height] }
+	}
+
+	pub fn from_fn(width: usize, height: usize, mut color: impl FnMut(usize, usize) -> Rgb) -> Self {
+		let pixels = (0 .. height).flat_map(|y| (0 .. width).map(move |x| (x, y))).map(|(x, y)| color(x, y)).collect();
+
+		Self { width, height, pixels }
+	}
+
+	/// Renders each cell of `grid` as one pixel, the min corner of the grid is the top left pixel.
+	pub fn from_grid<T>(grid: &Grid2<T>, mut color: impl FnMut(&T) -> Rgb) -> Self {
+		let min = grid.bounding_box().min;
+		Self::from_fn(
+			grid.width().max(0) as usize,
+			grid.height().max(0) as usize,
+			|x, y| color(grid.get(min + Point::<2>::new(x as isize, y as isize)).unwrap())
+		)
+	}
+
+	/// Same as [`Image::from_grid`] with the colours of the cell [`Palette`].
+	pub fn from_palette<T: Palette>(grid: &Grid2<T>) -> Self {
+		Self::from_grid(grid, Palette::color)
+	}
+
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	pub fn height(&self) -> usize {
+		self.height
+	}
+
+	pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+		if x >= self.width {
+			return None;
+		}
+
+		self.pixels.get(y * self.width + x).copied()
+	}
+
+	pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+		if x < self.width && y < self.height {
+			self.pixels[y * self.width + x] = color;
+		}
+	}
+
+	/// Enlarges the image so that each pixel becomes a `scale` by `scale` square.
+	pub fn scaled(&self, scale: usize) -> Self {
+		Self::from_fn(self.width * scale, self.height * scale, |x, y| self.pixels[y / scale * self.width + x / scale])
+	}
+
+	pub fn write_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+		write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+		out.write_all(&self.pixels.concat())
+	}
+
+	/// Writes the luma of each pixel as a grayscale image.
+	pub fn write_pgm(&self, out: &mut impl Write) -> io::Result<()> {
+		write!(out, "P5\n{} {}\n255\n", self.width, self.height)?;
+		out.write_all(&self.pixels.iter().copied().map(luma).collect::<Vec<u8>>())
+	}
+
+	/// Writes an 8-bit RGB PNG without compression.
+	pub fn write_png(&self, out: &mut impl Write) -> io::Result<()> {
+		if self.width == 0 || self.height == 0 {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, "PNG image cannot be empty"));
+		}
+
+		out.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+		let mut header = Vec::with_capacity(13);
+		header.extend((self.width as u32).to_be_bytes());
+		header.extend((self.height as u32).to_be_bytes());
+		// bit depth 8, colour type RGB, default compression, filter and interlace methods
+		header.extend([8, 2, 0, 0, 0]);
+		png::write_chunk(out, b"IHDR", &header)?;
+
+		// each scanline starts with filter type 0 (none)
+		let mut scanlines = Vec::with_capacity(self.height * (self.width * 3 + 1));
+		for row in self.pixels.chunks(self.width) {
+			scanlines.push(0);
+			scanlines.extend(row.concat());
+		}
+		png::write_chunk(out, b"IDAT", &png::zlib_stored(&scanlines))?;
+
+		png::write_chunk(out, b"IEND", &[])
+	}
+
+	pub fn write(&self, out: &mut impl Write, format: ImageFormat) -> io::Result<()> {
+		match format {
+			ImageFormat::Png => self.write_png(out),
+			ImageFormat::Ppm => self.write_ppm(out),
+			ImageFormat::Pgm => self.write_pgm(out)
+		}
+	}
+
+	pub fn save(&self, path: &Path, format: ImageFormat) -> anyhow::Result<()> {
+		let file = File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+		let mut out = BufWriter::new(file);
+		self.write(&mut out, format).and_then(|_| out.flush()).with_context(|| format!("Failed to write {}", path.display()))
+	}
+}
+
+mod png {
+	use std::io::{self, Write};
+
+	const CRC_TABLE: [u32; 256] = {
+		let mut table = [0; 256];
+		let mut n = 0;
+		while n < 256 {
+			let mut c = n as u32;
+			let mut k = 0;
+			while k < 8 {
+				c = if c & 1 == 1 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+				k += 1;
+			}
+			table[n] = c;
+			n += 1;
+		}
+
+		table
+	};
+
+	pub fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+		!bytes.into_iter().fold(!0u32, |crc, &b| CRC_TABLE[((crc ^ b as u32) & 0xFF) as usize] ^ (crc >> 8))
+	}
+
+	pub fn adler32(bytes: &[u8]) -> u32 {
+		const MOD: u32 = 65521;
+
+		let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), &byte| {
+			let a = (a + byte as u32) % MOD;
+			(a, (b + a) % MOD)
+		});
+
+		(b << 16) | a
+	}
+
+	/// Wraps `data` in a zlib stream of uncompressed deflate blocks.
+	pub fn zlib_stored(data: &[u8]) -> Vec<u8> {
+		const BLOCK: usize = u16::MAX as usize;
+
+		let blocks = data.len().div_ceil(BLOCK).max(1);
+		let mut result = Vec::with_capacity(data.len() + blocks * 5 + 6);
+		// deflate with 32K window, no preset dictionary, check bits make the header divisible by 31
+		result.extend([0x78, 0x01]);
+		for index in 0 .. blocks {
+			let block = &data[index * BLOCK .. data.len().min((index + 1) * BLOCK)];
+			let len = block.len() as u16;
+
+			result.push((index + 1 == blocks) as u8);
+			result.extend(len.to_le_bytes());
+			result.extend((!len).to_le_bytes());
+			result.extend(block);
+		}
+		result.extend(adler32(data).to_be_bytes());
+
+		result
+	}
+
+	pub fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+		out.write_all(&(data.len() as u32).to_be_bytes())?;
+		out.write_all(kind)?;
+		out.write_all(data)?;
+		out.write_all(&crc32(kind.iter().chain(data)).to_be_bytes())
+	}
+}
+
+/// Where frames recorded by [`record_frame`] are written, set from the `--frames` CLI options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameConfig {
+	pub dir: PathBuf,
+	pub format: ImageFormat,
+	/// Size of a grid cell in pixels.
+	pub scale: usize
+}
+impl FrameConfig {
+	pub fn new(dir: PathBuf) -> Self {
+		Self { dir, format: ImageFormat::default(), scale: 4 }
+	}
+}
+
+struct FrameRecorder {
+	config: FrameConfig,
+	/// Number of frames recorded so far under each name.
+	counters: BTreeMap<String, usize>
+}
+
+static FRAMES: Mutex<Option<FrameRecorder>> = Mutex::new(None);
+
+/// Enables or disables recording of frames for the whole process, creating the output directory if needed.
+pub fn set_frame_config(config: Option<FrameConfig>) -> anyhow::Result<()> {
+	if let Some(config) = config.as_ref() {
+		std::fs::create_dir_all(&config.dir).with_context(|| format!("Failed to create frame directory {}", config.dir.display()))?;
+	}
+
+	*FRAMES.lock().unwrap_or_else(|err| err.into_inner()) = config.map(|config| FrameRecorder { config, counters: BTreeMap::new() });
+
+	Ok(())
+}
+
+pub fn frames_enabled() -> bool {
+	FRAMES.lock().map(|frames| frames.is_some()).unwrap_or(false)
+}
+
+/// Writes the next frame of the sequence `name` as `<dir>/<name>_NNNNN.<ext>`, does nothing when frames are disabled.
+///
+/// The image is only rendered when it is going to be written.
+pub fn record_frame(name: &str, image: impl FnOnce() -> Image) -> anyhow::Result<()> {
+	let mut frames = FRAMES.lock().unwrap_or_else(|err| err.into_inner());
+	let Some(recorder) = frames.as_mut() else {
+		return Ok(());
+	};
+
+	let counter = recorder.counters.entry(name.to_string()).or_insert(0);
+	let path = recorder.config.dir.join(format!("{}_{:05}.{}", name, counter, recorder.config.format.extension()));
+	*counter += 1;
+
+	image().scaled(recorder.config.scale.max(1)).save(&path, recorder.config.format)
+}
+
+/// Same as [`record_frame`] with the grid rendered using its [`Palette`].
+pub fn record_grid<T: Palette>(name: &str, grid: &Grid2<T>) -> anyhow::Result<()> {
+	record_frame(name, || Image::from_palette(grid))
+}
+
+#[cfg(test)]
+mod test {
+	use crate::geometry::{Grid2, Point};
+
+	use super::{png, record_grid, set_frame_config, FrameConfig, Image, ImageFormat, Palette};
+
+	#[derive(Clone, Copy)]
+	enum Cell {
+		Wall,
+		Floor
+	}
+	impl Palette for Cell {
+		fn color(&self) -> [u8; 3] {
+			match self {
+				Self::Wall => [200, 10, 10],
+				Self::Floor => [0, 0, 255]
+			}
+		}
+	}
+
+	fn grid() -> Grid2<Cell> {
+		let mut grid = Grid2::new_width(vec![Cell::Floor, Cell::Wall, Cell::Wall, Cell::Floor, Cell::Floor, Cell::Floor], 3).unwrap();
+		grid.shift(Point::<2>::new(-5, 10));
+
+		grid
+	}
+
+	#[test]
+	fn test_checksums() {
+		assert_eq!(png::crc32(b"123456789"), 0xCBF4_3926);
+		assert_eq!(png::crc32(b"IEND"), 0xAE42_6082);
+		assert_eq!(png::adler32(b"Wikipedia"), 0x11E6_0398);
+	}
+
+	#[test]
+	fn test_image() {
+		let image = Image::from_palette(&grid());
+		assert_eq!((image.width(), image.height()), (3, 2));
+		assert_eq!(image.get(1, 0), Some([200, 10, 10]));
+		assert_eq!(image.get(0, 1), Some([0, 0, 255]));
+		assert_eq!(image.get(3, 0), None);
+
+		let scaled = image.scaled(2);
+		assert_eq!((scaled.width(), scaled.height()), (6, 4));
+		assert_eq!(scaled.get(3, 1), Some([200, 10, 10]));
+		assert_eq!(scaled.get(3, 2), Some([0, 0, 255]));
+
+		let mut ppm = Vec::new();
+		image.write_ppm(&mut ppm).unwrap();
+		assert!(ppm.starts_with(b"P6\n3 2\n255\n"));
+		assert_eq!(ppm.len(), 11 + 3 * 2 * 3);
+		assert_eq!(&ppm[14 .. 17], &[200, 10, 10]);
+
+		let mut pgm = Vec::new();
+		image.write_pgm(&mut pgm).unwrap();
+		assert_eq!(pgm, b"P5\n3 2\n255\n\x1d\x42\x42\x1d\x1d\x1d");
+
+		assert_eq!("ppm".parse::<ImageFormat>().unwrap(), ImageFormat::Ppm);
+		assert!("gif".parse::<ImageFormat>().is_err());
+	}
+
+	#[test]
+	fn test_png() {
+		// wider than one stored block
+		let image = Image::from_fn(30_000, 2, |x, y| [x as u8, y as u8, 7]);
+		let mut data = Vec::new();
+		image.write_png(&mut data).unwrap();
+		assert!(data.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+		let mut chunks = Vec::new();
+		let mut rest = &data[8 ..];
+		while !rest.is_empty() {
+			let len = u32::from_be_bytes(rest[0 .. 4].try_into().unwrap()) as usize;
+			let (kind, body) = (&rest[4 .. 8], &rest[8 .. 8 + len]);
+			let crc = u32::from_be_bytes(rest[8 + len .. 12 + len].try_into().unwrap());
+			assert_eq!(crc, png::crc32(kind.iter().chain(body)));
+
+			chunks.push((kind.to_vec(), body.to_vec()));
+			rest = &rest[12 + len ..];
+		}
+		let kinds: Vec<&[u8]> = chunks.iter().map(|(kind, _)| kind.as_slice()).collect();
+		assert_eq!(kinds, vec![b"IHDR".as_slice(), b"IDAT", b"IEND"]);
+		assert_eq!(chunks[0].1, [0, 0, 0x75, 0x30, 0, 0, 0, 2, 8, 2, 0, 0, 0]);
+
+		// unpack stored deflate blocks
+		let zlib = &chunks[1].1;
+		assert_eq!((zlib[0] as u16 * 256 + zlib[1] as u16) % 31, 0);
+		let mut inflated = Vec::new();
+		let mut at = 2;
+		loop {
+			let last = zlib[at] & 1 == 1;
+			let len = u16::from_le_bytes([zlib[at + 1], zlib[at + 2]]);
+			assert_eq!(!len, u16::from_le_bytes([zlib[at + 3], zlib[at + 4]]));
+			inflated.extend(&zlib[at + 5 .. at + 5 + len as usize]);
+			at += 5 + len as usize;
+			if last {
+				break;
+			}
+		}
+		assert_eq!(u32::from_be_bytes(zlib[at ..].try_into().unwrap()), png::adler32(&inflated));
+
+		assert_eq!(inflated.len(), 2 * (1 + 30_000 * 3));
+		assert_eq!(&inflated[0 .. 7], &[0, 0, 0, 7, 1, 0, 7]);
+		assert_eq!(&inflated[90_001 .. 90_005], &[0, 0, 1, 7]);
+
+		assert!(Image::new(0, 5, [0; 3]).write_png(&mut Vec::new()).is_err());
+		assert_eq!(png::zlib_stored(&[]), [0x78, 0x01, 1, 0, 0, 0xFF, 0xFF, 0, 0, 0, 1]);
+	}
+
+	#[test]
+	fn test_record_frames() {
+		let dir = std::env::temp_dir().join(format!("aoc_commons_frames_{}", std::process::id()));
+
+		// disabled by default
+		record_grid("test", &grid()).unwrap();
+		assert!(!dir.exists());
+
+		set_frame_config(Some(FrameConfig { format: ImageFormat::Ppm, scale: 2, ..FrameConfig::new(dir.clone()) })).unwrap();
+		record_grid("test", &grid()).unwrap();
+		record_grid("test", &grid()).unwrap();
+		record_grid("other", &grid()).unwrap();
+		set_frame_config(None).unwrap();
+		record_grid("test", &grid()).unwrap();
+
+		let mut files: Vec<String> = std::fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name().into_string().unwrap()).collect();
+		files.sort();
+		assert_eq!(files, vec!["other_00000.ppm", "test_00000.ppm", "test_00001.ppm"]);
+		assert!(std::fs::read(dir.join("test_00001.ppm")).unwrap().starts_with(b"P6\n6 4\n255\n"));
+
+		std::fs::remove_dir_all(&dir).unwrap();
+	}
+}
@@ -1,4 +1,4 @@
-use std::{path::{PathBuf, Path}, io::Read};
+use std::{fmt, path::{PathBuf, Path}, io::Read};
 
 use anyhow::Context;
 
@@ -6,72 +6,351 @@ pub use anyhow;
 pub use log;
 
 pub mod macros;
+pub mod bench;
 pub mod geometry;
 pub mod combinatorics;
+pub mod cycle;
+pub mod image;
+pub mod logging;
+pub mod numeric;
 pub mod perfect_hash;
+pub mod player;
+pub mod polygon;
+pub mod puzzle;
+pub mod range_set;
+pub mod search;
 
-pub fn setup_logger(level: log::Level) -> anyhow::Result<()> {
-	use edwardium_logger::{
-		Logger,
-		targets::stderr::StderrTarget
-	};
+pub use logging::{setup_logger, LogConfig};
 
-	let logger = Logger::new(
-		StderrTarget::new(level, Default::default()),
-		std::time::Instant::now()
-	);
-	logger.init_boxed().context("Could not initialize logger")?;
+/// Source of puzzle input, `-` on the command line stands for stdin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+	Stdin,
+	File(PathBuf)
+}
+impl Input {
+	pub fn open(&self) -> anyhow::Result<Box<dyn Read>> {
+		match self {
+			Self::Stdin => Ok(Box::new(std::io::stdin())),
+			Self::File(path) => Ok(Box::new(read_file(path)?))
+		}
+	}
+
+	pub fn read_to_string(&self) -> anyhow::Result<String> {
+		let mut result = String::new();
+		self.open()?.read_to_string(&mut result).with_context(|| format!("Failed to read input {}", self))?;
 
-	Ok(())
+		Ok(result)
+	}
+}
+impl From<&str> for Input {
+	fn from(value: &str) -> Self {
+		match value {
+			"-" => Self::Stdin,
+			path => Self::File(PathBuf::from(path))
+		}
+	}
+}
+impl fmt::Display for Input {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Stdin => write!(f, "<stdin>"),
+			Self::File(path) => write!(f, "{}", path.display())
+		}
+	}
 }
 
+#[derive(Debug)]
 pub struct Cli {
-	pub input: PathBuf,
-	pub log_level: log::Level
+	pub inputs: Vec<Input>,
+	pub log: LogConfig,
+	/// Only run this part of the puzzle, both when `None`.
+	pub part: Option<u8>,
+	/// Values of puzzles which are not part of their input, see [`puzzle::Puzzle::parse_with`].
+	pub parameters: puzzle::Parameters,
+	pub bench: bench::BenchConfig,
+	/// Directory and format of recorded simulation frames, disabled when `None`.
+	pub frames: Option<image::FrameConfig>,
+	/// Show simulation frames in the terminal, disabled when `None`.
+	pub play: Option<player::PlayerConfig>
+}
+impl Cli {
+	const USAGE: &'static str = "Usage: [OPTIONS] [INPUT|-]...
+Reads inputs/inputNN.txt when no input is given, where NN is the puzzle number.
+Options:
+  --log-level off|error|warn|info|debug|trace
+                   default level, also of modules not named in --log
+  --log FILTER     per-module log filter, e.g. synacor::cpu=warn,synacor=debug or puzzle17=trace
+                   (default from $AOC_LOG)
+  --log-file PATH  also append logs to PATH
+  --log-file-filter FILTER
+                   log filter of the file (default trace)
+  --log-time elapsed|timestamp|none
+  --part 1|2       run only one part of the puzzle
+  --param NAME=VALUE
+                   set a value the input does not contain, e.g. the area of an example
+  --time           print how long parsing and each part took
+  --bench RUNS     solve RUNS times and print timing statistics
+  --bench-csv PATH append timing statistics to PATH in hyperfine CSV format
+  --example        use inputs/inputNNex.txt where NN is the puzzle number
+  --kind KIND      use inputs/inputNN<KIND>.txt where NN is the puzzle number
+  --frames DIR     write simulation frames to DIR
+  --frames-format png|ppm|pgm
+  --frames-scale N size of a grid cell in pixels (default 4)
+  --play FPS       animate simulations in the terminal, plain frames when stdout is not a terminal";
+
+	pub fn run_part(&self, part: u8) -> bool {
+		self.part.map(|p| p == part).unwrap_or(true)
+	}
+
+	/// Resolves `inputs/inputNN<KIND>.txt` where NN are the trailing digits of the program name (e.g. `puzzle17`).
+	fn input_of_kind(program: &str, kind: &str) -> anyhow::Result<Input> {
+		let name = Path::new(program).file_stem().and_then(|s| s.to_str()).unwrap_or(program);
+		let number = name.trim_start_matches(|c: char| !c.is_ascii_digit());
+		anyhow::ensure!(!number.is_empty(), "Cannot determine puzzle number from program name \"{}\"", name);
+
+		Ok(Input::File(PathBuf::from(format!("inputs/input{}{}.txt", number, kind))))
+	}
 }
+
 pub fn parse_cli() -> anyhow::Result<Cli> {
-	let mut it = std::env::args().skip(1);
-
-		let mut log_level: Option<log::Level> = None;
-		let mut input: Option<PathBuf> = None;
-
-		while let Some(arg) = it.next() {
-			match arg.as_str() {
-				"--log-level" => {
-					log_level = Some(
-						match it.next().context("--log-level requires a value")?.as_str() {
-							"trace" => log::Level::Trace,
-							"debug" => log::Level::Debug,
-							"info" => log::Level::Info,
-							"warn" => log::Level::Warn,
-							"error" => log::Level::Error,
-							v => anyhow::bail!("Invalid --log-level value: {}", v)
-						}
-					);
-				},
-				new_input if input.is_none() => { input = Some(PathBuf::from(new_input)); }
-				v => anyhow::bail!("Unknown argument: {}", v)
-			}
+	let mut args = std::env::args();
+	let program = args.next().unwrap_or_default();
+
+	parse_cli_from(&program, args)
+}
+
+pub fn parse_cli_from(program: &str, args: impl IntoIterator<Item = String>) -> anyhow::Result<Cli> {
+	let mut it = args.into_iter();
+
+	let mut log_level: Option<log::LevelFilter> = None;
+	let mut log_filter: Option<logging::LogFilter> = None;
+	let mut log_file: Option<PathBuf> = None;
+	let mut log_file_filter = logging::LogFilter::new(log::LevelFilter::Trace);
+	let mut log_time = logging::LogTime::default();
+	let mut part: Option<u8> = None;
+	let mut parameters = puzzle::Parameters::new();
+	let mut bench = bench::BenchConfig::default();
+	let mut inputs: Vec<Input> = Vec::new();
+	let mut frames_dir: Option<PathBuf> = None;
+	let mut frames_format = image::ImageFormat::default();
+	let mut frames_scale: usize = 4;
+	let mut play: Option<player::PlayerConfig> = None;
+
+	while let Some(arg) = it.next() {
+		match arg.as_str() {
+			"--log-level" => {
+				let level = it.next().context("--log-level requires a value")?;
+				let level = level.parse().map_err(|_| anyhow::anyhow!("Invalid --log-level value: {}", level))?;
+				log_level = Some(level);
+			},
+			"--log" => {
+				log_filter = Some(it.next().context("--log requires a value")?.parse().context("Invalid --log value")?);
+			},
+			"--log-file" => { log_file = Some(PathBuf::from(it.next().context("--log-file requires a value")?)); },
+			"--log-file-filter" => {
+				log_file_filter = it.next().context("--log-file-filter requires a value")?.parse().context("Invalid --log-file-filter value")?;
+			},
+			"--log-time" => { log_time = it.next().context("--log-time requires a value")?.parse()?; },
+			"--part" => {
+				part = Some(
+					match it.next().context("--part requires a value")?.as_str() {
+						"1" => 1,
+						"2" => 2,
+						v => anyhow::bail!("Invalid --part value: {}", v)
+					}
+				);
+			},
+			"--param" => {
+				parameters.set_assignment(&it.next().context("--param requires a value")?).context("Invalid --param value")?;
+			},
+			"--time" => { bench.time = true; },
+			"--bench" => {
+				let runs = it.next().context("--bench requires a value")?;
+				bench.runs = runs.parse().ok().filter(|&r| r > 0).with_context(|| format!("Invalid --bench value: {}", runs))?;
+			},
+			"--bench-csv" => { bench.csv = Some(PathBuf::from(it.next().context("--bench-csv requires a value")?)); },
+			"--example" => { inputs.push(Cli::input_of_kind(program, "ex")?); },
+			"--kind" => {
+				let kind = it.next().context("--kind requires a value")?;
+				inputs.push(Cli::input_of_kind(program, &kind)?);
+			},
+			"--frames" => { frames_dir = Some(PathBuf::from(it.next().context("--frames requires a value")?)); },
+			"--frames-format" => { frames_format = it.next().context("--frames-format requires a value")?.parse()?; },
+			"--frames-scale" => {
+				let scale = it.next().context("--frames-scale requires a value")?;
+				frames_scale = scale.parse().ok().filter(|&s| s > 0).with_context(|| format!("Invalid --frames-scale value: {}", scale))?;
+			},
+			"--play" => {
+				let fps = it.next().context("--play requires a value")?;
+				let fps = fps.parse().ok().filter(|&f: &f64| f > 0.0 && f.is_finite()).with_context(|| format!("Invalid --play value: {}", fps))?;
+				play = Some(player::PlayerConfig { fps });
+			},
+			v if v.starts_with("--") => anyhow::bail!("Unknown option: {}\n{}", v, Cli::USAGE),
+			v => { inputs.push(Input::from(v)); }
 		}
+	}
+
+	if inputs.is_empty() {
+		inputs.push(Cli::input_of_kind(program, "").with_context(|| format!("Missing input file\n{}", Cli::USAGE))?);
+	}
 
-		Ok(Cli {
-			input: input.context("Missing input file")?,
-			log_level: log_level.unwrap_or(log::Level::Info)
-		})
+	Ok(Cli {
+		inputs,
+		log: LogConfig {
+			// the level only replaces the default of the module filter, whichever of the two comes first
+			filter: match (log_filter, log_level) {
+				(Some(mut filter), Some(level)) => {
+					filter.set_default(level);
+					filter
+				},
+				(Some(filter), None) => filter,
+				(None, Some(level)) => logging::LogFilter::new(level),
+				(None, None) => logging::LogFilter::from_env()?
+			},
+			file: log_file.map(|path| (path, log_file_filter)),
+			time: log_time
+		},
+		part,
+		parameters,
+		bench,
+		frames: frames_dir.map(|dir| image::FrameConfig { dir, format: frames_format, scale: frames_scale }),
+		play
+	})
 }
 
 pub fn read_file(path: &Path) -> anyhow::Result<impl Read> {
 	use std::fs::OpenOptions;
 
-	let file = OpenOptions::new().read(true).open(path)?;
+	let file = OpenOptions::new().read(true).open(path).with_context(|| format!("Failed to open {}", path.display()))?;
 	
 	Ok(file)
 }
 
-pub fn initialize() -> anyhow::Result<impl Read> {
+/// Parses the CLI and sets up the logger.
+pub fn initialize_cli() -> anyhow::Result<Cli> {
 	let cli = parse_cli().context("Failed to parse CLI")?;
-	setup_logger(cli.log_level).context("Failed to set up logger")?;
-	let file = read_file(&cli.input).context("Failed to open input file")?;
+	setup_logger(&cli.log).context("Failed to set up logger")?;
+
+	Ok(cli)
+}
+
+/// Initializes the CLI and returns the single input, for puzzles which do not support multiple inputs or `--part`.
+pub fn initialize() -> anyhow::Result<impl Read> {
+	let cli = initialize_cli()?;
+	anyhow::ensure!(cli.inputs.len() == 1, "Only a single input is supported");
+	if cli.part.is_some() {
+		log::warn!("--part is not supported, running both parts");
+	}
+	if cli.parameters != puzzle::Parameters::default() {
+		log::warn!("--param is not supported");
+	}
+	if cli.bench != bench::BenchConfig::default() {
+		log::warn!("--time and --bench are not supported");
+	}
+	if cli.frames.is_some() || cli.play.is_some() {
+		log::warn!("--frames and --play are not supported");
+	}
+
+	let file = cli.inputs[0].open().context("Failed to open input file")?;
 
 	Ok(file)
 }
+
+#[cfg(test)]
+mod test {
+	use std::path::PathBuf;
+
+	use crate::{bench::BenchConfig, image::{FrameConfig, ImageFormat}, logging::{LogFilter, LogTime}, player::PlayerConfig, puzzle::Parameters};
+
+	use super::{parse_cli_from, Input};
+
+	fn parse(args: &[&str]) -> anyhow::Result<super::Cli> {
+		parse_cli_from("target/debug/puzzle07", args.iter().map(|s| s.to_string()))
+	}
+
+	#[test]
+	fn test_parse_cli_inputs() {
+		assert_eq!(parse(&["a.txt", "-"]).unwrap().inputs, vec![Input::from("a.txt"), Input::Stdin]);
+		assert_eq!(
+			parse(&["--example", "--kind", "_big"]).unwrap().inputs,
+			vec![Input::from("inputs/input07ex.txt"), Input::from("inputs/input07_big.txt")]
+		);
+		assert_eq!(parse(&[]).unwrap().inputs, vec![Input::from("inputs/input07.txt")]);
+		assert!(parse_cli_from("synacor", Vec::new()).is_err());
+
+		let err = parse(&["a.txt", "--verbose"]).unwrap_err().to_string();
+		assert!(err.starts_with("Unknown option: --verbose"));
+		assert!(err.contains("--part 1|2"));
+	}
+
+	#[test]
+	fn test_parse_cli_log() {
+		let cli = parse(&["a.txt", "--log-level", "debug"]).unwrap();
+		assert_eq!(cli.log.filter.level_for("puzzle07"), log::LevelFilter::Debug);
+		assert_eq!(cli.log.file, None);
+		assert!(parse(&["a.txt", "--log-level", "loud"]).is_err());
+	}
+
+	#[test]
+	fn test_parse_cli_log_filters() {
+		let cli = parse(&["a.txt", "--log", "synacor::cpu=warn,synacor=debug", "--log-level", "error", "--log-file", "trace.log", "--log-time", "none"]).unwrap();
+		assert_eq!(cli.log.filter.level_for("synacor::cpu"), log::LevelFilter::Warn);
+		assert_eq!(cli.log.filter.level_for("synacor::debugger"), log::LevelFilter::Debug);
+		assert_eq!(cli.log.filter.level_for("puzzle07"), log::LevelFilter::Error);
+		assert_eq!(cli.log.file, Some((PathBuf::from("trace.log"), LogFilter::new(log::LevelFilter::Trace))));
+		assert_eq!(cli.log.time, LogTime::None);
+
+		let cli = parse(&["a.txt", "--log-level", "error", "--log", "trace,synacor=debug"]).unwrap();
+		assert_eq!(cli.log.filter.level_for("synacor::cpu"), log::LevelFilter::Debug);
+		assert_eq!(cli.log.filter.level_for("puzzle07"), log::LevelFilter::Error);
+
+		assert!(parse(&["a.txt", "--log", "a=loud"]).is_err());
+	}
+
+	#[test]
+	fn test_parse_cli_part() {
+		let cli = parse(&["a.txt", "--part", "2"]).unwrap();
+		assert!(!cli.run_part(1));
+		assert!(cli.run_part(2));
+
+		let cli = parse(&["a.txt"]).unwrap();
+		assert!(cli.run_part(1) && cli.run_part(2));
+
+		assert!(parse(&["a.txt", "--part", "3"]).is_err());
+	}
+
+	#[test]
+	fn test_parse_cli_bench() {
+		let cli = parse(&["a.txt", "--time", "--bench", "10", "--bench-csv", "data/puzzle07.time.csv"]).unwrap();
+		assert_eq!(cli.bench, BenchConfig { time: true, runs: 10, csv: Some(PathBuf::from("data/puzzle07.time.csv")) });
+		assert_eq!(parse(&["a.txt"]).unwrap().bench, BenchConfig::default());
+		assert!(parse(&["a.txt", "--bench", "0"]).is_err());
+	}
+
+	#[test]
+	fn test_parse_cli_frames() {
+		let cli = parse(&["a.txt", "--frames", "frames", "--frames-format", "pgm"]).unwrap();
+		assert_eq!(cli.frames, Some(FrameConfig { format: ImageFormat::Pgm, ..FrameConfig::new(PathBuf::from("frames")) }));
+		assert_eq!(parse(&["a.txt", "--frames-scale", "1"]).unwrap().frames, None);
+		assert!(parse(&["a.txt", "--frames", "frames", "--frames-scale", "0"]).is_err());
+	}
+
+	#[test]
+	fn test_parse_cli_param() {
+		let cli = parse(&["a.txt", "--param", "row=10", "--param", "limit=20", "--param", "row=11"]).unwrap();
+		assert_eq!(cli.parameters.get_or("row", 0).unwrap(), 11);
+		assert_eq!(cli.parameters.get_or("limit", 0).unwrap(), 20);
+		assert_eq!(parse(&["a.txt"]).unwrap().parameters, Parameters::new());
+		assert!(parse(&["a.txt", "--param", "row"]).is_err());
+		assert!(parse(&["a.txt", "--param"]).is_err());
+	}
+
+	#[test]
+	fn test_parse_cli_play() {
+		assert_eq!(parse(&["a.txt", "--play", "12.5"]).unwrap().play, Some(PlayerConfig { fps: 12.5 }));
+		assert_eq!(parse(&["a.txt"]).unwrap().play, None);
+		assert!(parse(&["a.txt", "--play", "0"]).is_err());
+		assert!(parse(&["a.txt", "--play"]).is_err());
+	}
+}
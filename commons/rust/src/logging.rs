@@ -0,0 +1,301 @@
+use std::{
+	fmt::Write as _,
+	fs::{File, OpenOptions},
+	io::{self, IsTerminal, Write},
+	path::PathBuf,
+	str::FromStr,
+	sync::Mutex,
+	time::{Duration, SystemTime}
+};
+
+use anyhow::Context;
+use log::{Level, LevelFilter, Metadata, Record};
+
+use edwardium_logger::{
+	Logger,
+	target::{Target, Targets},
+	targets::util::Timestamp
+};
+
+/// Environment variable with filter directives, used when none are given on the command line.
+pub const LOG_ENV: &str = "AOC_LOG";
+
+/// Per-module level filter parsed from directives like `synacor::cpu=warn,synacor=debug,info`.
+///
+/// A directive without `=` is either a level which applies to all modules or a module name which is logged at all levels.
+/// A module matches whole path segments anywhere in the record target, so `puzzle17` also applies to
+/// `aoc2022::puzzle17` and its submodules. The longest matching module wins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilter {
+	default: LevelFilter,
+	/// Sorted by module length, longest first.
+	directives: Vec<(String, LevelFilter)>
+}
+impl LogFilter {
+	pub fn new(default: LevelFilter) -> Self {
+		Self { default, directives: Vec::new() }
+	}
+
+	/// Parses the filter from [`LOG_ENV`] or returns the `info` filter if the variable is not set.
+	pub fn from_env() -> anyhow::Result<Self> {
+		match std::env::var(LOG_ENV) {
+			Err(_) => Ok(Self::new(LevelFilter::Info)),
+			Ok(value) => value.parse().with_context(|| format!("Invalid {} value", LOG_ENV))
+		}
+	}
+
+	pub fn set_default(&mut self, level: LevelFilter) {
+		self.default = level;
+	}
+
+	/// Sets the level for `module` and all of its submodules, replacing any previous directive for the same module.
+	pub fn set_module(&mut self, module: &str, level: LevelFilter) {
+		self.directives.retain(|(m, _)| m != module);
+
+		let index = self.directives.partition_point(|(m, _)| m.len() >= module.len());
+		self.directives.insert(index, (module.to_string(), level));
+	}
+
+	pub fn level_for(&self, target: &str) -> LevelFilter {
+		self.directives.iter().find(|(module, _)| Self::matches(module, target)).map(|(_, level)| *level).unwrap_or(self.default)
+	}
+
+	fn matches(module: &str, target: &str) -> bool {
+		let is_module = |path: &str| path.strip_prefix(module).is_some_and(|rest| rest.is_empty() || rest.starts_with("::"));
+
+		is_module(target) || target.match_indices("::").any(|(index, separator)| is_module(&target[index + separator.len() ..]))
+	}
+
+	/// Most verbose level enabled for any module.
+	pub fn max_level(&self) -> LevelFilter {
+		self.directives.iter().map(|(_, level)| *level).fold(self.default, Ord::max)
+	}
+
+	pub fn enabled(&self, metadata: &Metadata) -> bool {
+		metadata.level() <= self.level_for(metadata.target())
+	}
+}
+impl FromStr for LogFilter {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut filter = Self::new(LevelFilter::Info);
+
+		for directive in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+			match directive.split_once('=') {
+				Some((module, level)) => {
+					let module = module.trim();
+					anyhow::ensure!(!module.is_empty(), "Missing module name in directive \"{}\"", directive);
+
+					let level = level.trim().parse().map_err(|_| anyhow::anyhow!("Invalid level in directive \"{}\"", directive))?;
+					filter.set_module(module, level);
+				}
+				None => match directive.parse() {
+					Ok(level) => filter.set_default(level),
+					Err(_) => filter.set_module(directive, LevelFilter::Trace)
+				}
+			}
+		}
+
+		Ok(filter)
+	}
+}
+
+/// Time prefix of log lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogTime {
+	/// Time elapsed since the logger was set up.
+	#[default]
+	Elapsed,
+	/// Wall clock time of day in UTC.
+	Timestamp,
+	None
+}
+impl FromStr for LogTime {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"elapsed" => Ok(Self::Elapsed),
+			"timestamp" => Ok(Self::Timestamp),
+			"none" => Ok(Self::None),
+			v => anyhow::bail!("Invalid log time format: {}", v)
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogConfig {
+	/// Filter of the stderr output.
+	pub filter: LogFilter,
+	/// Additional output appended to a file with its own filter.
+	pub file: Option<(PathBuf, LogFilter)>,
+	pub time: LogTime
+}
+impl LogConfig {
+	pub fn new(level: LevelFilter) -> Self {
+		Self { filter: LogFilter::new(level), file: None, time: LogTime::default() }
+	}
+}
+
+enum Output {
+	Stderr,
+	File(Mutex<File>)
+}
+
+struct Sink {
+	filter: LogFilter,
+	time: LogTime,
+	color: bool,
+	output: Output
+}
+impl Sink {
+	fn format(&self, duration_since_start: Duration, record: &Record) -> String {
+		let mut line = String::new();
+
+		match self.time {
+			LogTime::Elapsed => { let _ = write!(line, "[{}]", Timestamp::from(duration_since_start)); }
+			LogTime::Timestamp => {
+				let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+				let secs = now.as_secs() % (24 * 60 * 60);
+				let _ = write!(line, "[{:0>2}:{:0>2}:{:0>2}.{:0>3}]", secs / 3600, secs / 60 % 60, secs % 60, now.subsec_millis());
+			}
+			LogTime::None => ()
+		}
+
+		let _ = if self.color {
+			let color = match record.level() {
+				Level::Error => 31,
+				Level::Warn => 33,
+				Level::Info => 32,
+				Level::Debug => 34,
+				Level::Trace => 35
+			};
+			writeln!(line, "[\x1b[{}m{}\x1b[39m] ({}) {}", color, record.level(), record.target(), record.args())
+		} else {
+			writeln!(line, "[{}] ({}) {}", record.level(), record.target(), record.args())
+		};
+
+		line
+	}
+}
+impl Target for Sink {
+	type Error = io::Error;
+
+	fn level(&self) -> Level {
+		self.filter.max_level().to_level().unwrap_or(Level::Error)
+	}
+
+	fn ignore(&self, record: &Record) -> bool {
+		!self.filter.enabled(record.metadata())
+	}
+
+	fn write(&self, duration_since_start: Duration, record: &Record) -> io::Result<()> {
+		let line = self.format(duration_since_start, record);
+
+		match &self.output {
+			Output::Stderr => io::stderr().lock().write_all(line.as_bytes()),
+			Output::File(file) => file.lock().map_err(|_| io::Error::other("mutex poison error"))?.write_all(line.as_bytes())
+		}
+	}
+
+	fn flush(&self) -> io::Result<()> {
+		match &self.output {
+			Output::Stderr => io::stderr().flush(),
+			Output::File(file) => file.lock().map_err(|_| io::Error::other("mutex poison error"))?.flush()
+		}
+	}
+}
+
+struct Sinks(Vec<Sink>);
+impl Targets for Sinks {
+	type Results = io::Result<()>;
+
+	fn max_level(&self) -> LevelFilter {
+		self.0.iter().map(|sink| sink.filter.max_level()).fold(LevelFilter::Off, Ord::max)
+	}
+
+	fn write(&self, duration_since_start: Duration, record: &Record) -> Self::Results {
+		self.0.iter().filter(|sink| !sink.ignore(record)).try_for_each(|sink| Target::write(sink, duration_since_start, record))
+	}
+
+	fn flush(&self) -> Self::Results {
+		self.0.iter().try_for_each(Target::flush)
+	}
+}
+
+pub fn setup_logger(config: &LogConfig) -> anyhow::Result<()> {
+	let mut sinks = vec![Sink {
+		filter: config.filter.clone(),
+		time: config.time,
+		color: io::stderr().is_terminal(),
+		output: Output::Stderr
+	}];
+
+	if let Some((path, filter)) = config.file.as_ref() {
+		let file = OpenOptions::new().append(true).create(true).open(path).with_context(|| format!("Failed to open log file {}", path.display()))?;
+		sinks.push(Sink {
+			filter: filter.clone(),
+			time: config.time,
+			color: false,
+			output: Output::File(Mutex::new(file))
+		});
+	}
+
+	let logger = Logger::new(Sinks(sinks), std::time::Instant::now());
+	logger.init_boxed().context("Could not initialize logger")?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use log::LevelFilter;
+
+	use super::{LogFilter, LogTime};
+
+	#[test]
+	fn test_filter() {
+		let filter: LogFilter = "synacor::cpu=warn, synacor=debug".parse().unwrap();
+		assert_eq!(filter.level_for("synacor::cpu"), LevelFilter::Warn);
+		assert_eq!(filter.level_for("synacor::cpu::alu"), LevelFilter::Warn);
+		assert_eq!(filter.level_for("synacor::debugger"), LevelFilter::Debug);
+		assert_eq!(filter.level_for("synacor"), LevelFilter::Debug);
+		assert_eq!(filter.level_for("synacor_tools"), LevelFilter::Info);
+		assert_eq!(filter.level_for("puzzle17"), LevelFilter::Info);
+		assert_eq!(filter.max_level(), LevelFilter::Debug);
+
+		let filter: LogFilter = "off,puzzle17,aoc_commons::search=TRACE".parse().unwrap();
+		assert_eq!(filter.level_for("puzzle12"), LevelFilter::Off);
+		assert_eq!(filter.level_for("puzzle17"), LevelFilter::Trace);
+		assert_eq!(filter.level_for("aoc_commons::search"), LevelFilter::Trace);
+		assert_eq!(filter.level_for("aoc_commons::cycle"), LevelFilter::Off);
+
+		// modules also match inside of the target path
+		assert_eq!(filter.level_for("aoc2022::puzzle17"), LevelFilter::Trace);
+		assert_eq!(filter.level_for("aoc2022::puzzle17::tower"), LevelFilter::Trace);
+		assert_eq!(filter.level_for("aoc2022::puzzle170"), LevelFilter::Off);
+		assert_eq!(filter.level_for("aoc2022::xpuzzle17"), LevelFilter::Off);
+		assert_eq!(filter.level_for("tools::aoc_commons::search::bfs"), LevelFilter::Trace);
+
+		// later directives replace earlier ones
+		let filter: LogFilter = "a=warn,a=error,debug".parse().unwrap();
+		assert_eq!(filter.level_for("a"), LevelFilter::Error);
+		assert_eq!(filter, {
+			let mut expected = LogFilter::new(LevelFilter::Debug);
+			expected.set_module("a", LevelFilter::Error);
+			expected
+		});
+
+		assert!("a=loud".parse::<LogFilter>().is_err());
+		assert!("=info".parse::<LogFilter>().is_err());
+		assert_eq!("".parse::<LogFilter>().unwrap(), LogFilter::new(LevelFilter::Info));
+	}
+
+	#[test]
+	fn test_time() {
+		assert_eq!("none".parse::<LogTime>().unwrap(), LogTime::None);
+		assert_eq!("timestamp".parse::<LogTime>().unwrap(), LogTime::Timestamp);
+		assert!("uptime".parse::<LogTime>().is_err());
+	}
+}
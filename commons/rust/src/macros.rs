@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{fmt, iter::Peekable, num::NonZeroUsize, ops::Range, str::FromStr};
 
 pub struct FromStrToTryFromAdapter<T: FromStr>(pub T);
 impl<'a, T: FromStr> TryFrom<&'a str> for FromStrToTryFromAdapter<T> {
@@ -11,6 +11,146 @@ impl<'a, T: FromStr> TryFrom<&'a str> for FromStrToTryFromAdapter<T> {
 	}
 }
 
+/// Error of [`match_tokens!`] pointing at the offending token.
+///
+/// The source line and its number are not known to the macro, they can be attached afterwards with
+/// [`TokenError::in_line`] and [`TokenError::at_line_number`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenError {
+	pub message: String,
+	/// Index of the offending token, the number of tokens if the input ended early.
+	pub index: usize,
+	/// The offending token, `None` if the input ended early.
+	pub token: Option<String>,
+	pub line: Option<String>,
+	/// Byte range of the offending token in `line`.
+	pub span: Option<Range<usize>>,
+	pub line_number: Option<NonZeroUsize>,
+	/// Address of the token within the source, used to find it in the line.
+	address: usize
+}
+impl TokenError {
+	/// Attaches the source line, `line` must be the string the tokens were split from to locate the token.
+	pub fn in_line(mut self, line: &str) -> Self {
+		let start = line.as_ptr() as usize;
+		let address = self.address;
+		self.span = match self.token.as_ref() {
+			Some(token) if address >= start && address + token.len() <= start + line.len() => {
+				Some(address - start .. address - start + token.len())
+			}
+			Some(_) => None,
+			// ended early, point after the end of the line
+			None => Some(line.len() .. line.len())
+		};
+		self.line = Some(line.to_string());
+
+		self
+	}
+
+	/// Attaches the line number, starting at 1.
+	pub fn at_line_number(mut self, line_number: usize) -> Self {
+		self.line_number = NonZeroUsize::new(line_number);
+
+		self
+	}
+
+	/// Renders the line with a caret under the offending token, if the line is known.
+	pub fn snippet(&self) -> Option<String> {
+		let line = self.line.as_ref()?;
+		let span = self.span.as_ref()?;
+
+		let padding: String = line[.. span.start].chars().map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+		let carets = "^".repeat(line[span.clone()].chars().count().max(1));
+
+		Some(format!("  {}\n  {}{}", line, padding, carets))
+	}
+}
+impl fmt::Display for TokenError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if let Some(line_number) = self.line_number {
+			write!(f, "line {}, ", line_number)?;
+		}
+		match self.token.as_ref() {
+			Some(token) => write!(f, "token {} {:?}: {}", self.index, token, self.message),
+			None => write!(f, "token {} (end of input): {}", self.index, self.message)
+		}
+	}
+}
+impl std::error::Error for TokenError {}
+
+/// Iterator adapter used by [`match_tokens!`] to track the position of the current token.
+pub struct Tokens<'a, I: Iterator<Item = &'a str>> {
+	inner: Peekable<I>,
+	consumed: usize,
+	current: Option<&'a str>
+}
+impl<'a, I: Iterator<Item = &'a str>> Tokens<'a, I> {
+	pub fn new(inner: I) -> Self {
+		Self { inner: inner.peekable(), consumed: 0, current: None }
+	}
+
+	pub fn peek(&mut self) -> Option<&&'a str> {
+		self.inner.peek()
+	}
+
+	/// Error at the token last returned by [`Iterator::next`].
+	pub fn error(&self, message: impl Into<String>) -> TokenError {
+		TokenError {
+			message: message.into(),
+			index: if self.current.is_some() { self.consumed - 1 } else { self.consumed },
+			token: self.current.map(str::to_string),
+			line: None,
+			span: None,
+			line_number: None,
+			address: self.current.map(|token| token.as_ptr() as usize).unwrap_or(0)
+		}
+	}
+
+	/// Parses the token last returned by [`Iterator::next`] as `T`.
+	pub fn parse<T: TryFrom<&'a str>>(&self, token: &'a str) -> Result<T, TokenError> where T::Error: fmt::Display {
+		T::try_from(token).map_err(
+			|err| self.error(format!("Failed to parse as {}: {}", short_type_name(std::any::type_name::<T>()), err))
+		)
+	}
+}
+impl<'a, I: Iterator<Item = &'a str>> Iterator for Tokens<'a, I> {
+	type Item = &'a str;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.current = self.inner.next();
+		if self.current.is_some() {
+			self.consumed += 1;
+		}
+
+		self.current
+	}
+}
+
+/// Strips module paths and the [`FromStrToTryFromAdapter`] wrapper from a type name.
+fn short_type_name(name: &str) -> String {
+	let mut result = String::new();
+	let mut segment = String::new();
+	let mut chars = name.chars().peekable();
+	while let Some(c) = chars.next() {
+		if c == ':' && chars.peek() == Some(&':') {
+			chars.next();
+			segment.clear();
+		} else if c.is_alphanumeric() || c == '_' {
+			segment.push(c);
+		} else {
+			result.push_str(&segment);
+			segment.clear();
+			result.push(c);
+		}
+	}
+	result.push_str(&segment);
+
+	match result.strip_prefix("FromStrToTryFromAdapter<").and_then(|r| r.strip_suffix('>')) {
+		Some(inner) => inner.to_string(),
+		None => result
+	}
+}
+
 /// Match str tokens from the iterator.
 /// 
 /// Usage:
@@ -24,6 +164,49 @@ impl<'a, T: FromStr> TryFrom<&'a str> for FromStrToTryFromAdapter<T> {
 /// assert_eq!(from, "0");
 /// assert_eq!(to, vec![17, 21]);
 /// ```
+///
+/// A repetition followed by a literal ends before the first token matching the literal:
+/// ```
+/// # use aoc_commons::{macros::FromStrToTryFromAdapter, match_tokens};
+/// #
+/// let value = "items 79, 98 test 23";
+/// let (items, test) = match_tokens!(value.split([' ', ',']).filter(|s| !s.is_empty()); "items", ...items: Vec<FromStrToTryFromAdapter<u32>> {.0}, "test", test: FromStrToTryFromAdapter<u32> {.0}).unwrap();
+///
+/// assert_eq!(items, vec![79, 98]);
+/// assert_eq!(test, 23);
+/// ```
+///
+/// `name: Option<T>` is `None` without consuming the token if it cannot be parsed as `T`,
+/// `[...]` is an optional segment which is matched only if the next token matches its leading literal.
+/// A named segment `name: [...]` binds an `Option` of its captures:
+/// ```
+/// # use aoc_commons::{macros::{FromStrToTryFromAdapter, TokenError}, match_tokens};
+/// #
+/// fn parse(value: &str) -> Result<(Option<u32>, &str, Option<u32>), TokenError> {
+///     match_tokens!(
+///         value.split(' ');
+///         "add", amount: Option<FromStrToTryFromAdapter<u32>> {.0}, ["to"], target: &str, repeat: ["times", count: FromStrToTryFromAdapter<u32> {.0}]
+///     )
+/// }
+///
+/// assert_eq!(parse("add 5 to x times 3").unwrap(), (Some(5), "x", Some(3)));
+/// assert_eq!(parse("add y").unwrap(), (None, "y", None));
+/// assert!(parse("add x times").is_err());
+/// ```
+///
+/// Alternatives can be bound to values, e.g. enum variants:
+/// ```
+/// # use aoc_commons::match_tokens;
+/// #
+/// #[derive(Debug, PartialEq)]
+/// enum Op { Add, Mul }
+///
+/// let parse = |value: &str| match_tokens!(value.split(' '); "new", "=", "old", op: ("+" | "plus" => Op::Add, "*" => Op::Mul), "old");
+///
+/// assert_eq!(parse("new = old * old").unwrap(), Op::Mul);
+/// assert_eq!(parse("new = old plus old").unwrap(), Op::Add);
+/// assert!(parse("new = old - old").is_err());
+/// ```
 #[macro_export]
 macro_rules! match_tokens {
 	(
@@ -31,7 +214,7 @@ macro_rules! match_tokens {
 		$($rest: tt)+
 	) => {
 		{
-			let mut iter = $input;
+			let mut iter = $crate::macros::Tokens::new($input);
 			$crate::match_tokens!(__internal iter; $($rest)+ ,)
 		}
 	};
@@ -43,7 +226,57 @@ macro_rules! match_tokens {
 	) => {
 		match $iter.next() {
 			Some($head $(| $heads)*) => $crate::match_tokens!(__internal $iter $(, $partial)*; $($rest)*),
-			res => Err($crate::anyhow::anyhow!(concat!("Expected literal \"", $head, "\" but found {:?}"), res))
+			_ => Err($iter.error(concat!("Expected literal ", stringify!($head) $(, " or ", stringify!($heads))*)))
+		}
+	};
+
+	// optional segment
+	(
+		__internal $iter: expr $(, $partial: expr)*;
+		[ $first: literal $(| $firsts: literal)* $(, $($segment: tt)*)? ], $($rest: tt)*
+	) => {
+		match $iter.peek() {
+			Some(&($first $(| $firsts)*)) => match $crate::match_tokens!(__internal $iter; $first $(| $firsts)*, $($($segment)*)? ,) {
+				Ok(_) => $crate::match_tokens!(__internal $iter $(, $partial)*; $($rest)*),
+				Err(err) => Err(err)
+			},
+			_ => $crate::match_tokens!(__internal $iter $(, $partial)*; $($rest)*)
+		}
+	};
+
+	// ident-optional segment
+	(
+		__internal $iter: expr $(, $partial: expr)*;
+		$head: ident: [ $first: literal $(| $firsts: literal)* $(, $($segment: tt)*)? ], $($rest: tt)*
+	) => {
+		{
+			let $head = match $iter.peek() {
+				Some(&($first $(| $firsts)*)) => Some($crate::match_tokens!(__internal $iter; $first $(| $firsts)*, $($($segment)*)? ,)),
+				_ => None
+			};
+
+			match $head.transpose() {
+				Ok($head) => $crate::match_tokens!(__internal $iter $(, $partial)*, $head; $($rest)*),
+				Err(err) => Err(err)
+			}
+		}
+	};
+
+	// ident-alternatives
+	(
+		__internal $iter: expr $(, $partial: expr)*;
+		$head: ident: ( $($($alternative: literal)|+ => $value: expr),+ $(,)? ), $($rest: tt)*
+	) => {
+		{
+			let $head = match $iter.next() {
+				$(Some($($alternative)|+) => Ok($value),)+
+				_ => Err($iter.error(concat!("Expected ", stringify!($head), " to be one of", $($(" ", stringify!($alternative),)+)+)))
+			};
+
+			match $head {
+				Ok($head) => $crate::match_tokens!(__internal $iter $(, $partial)*, $head; $($rest)*),
+				Err(err) => Err(err)
+			}
 		}
 	};
 
@@ -57,9 +290,9 @@ macro_rules! match_tokens {
 			let result = loop {
 				match $iter.next() {
 					None => break Ok(()),
-					Some(val) => match <$head_ty>::try_from(val) {
+					Some(val) => match $iter.parse::<$head_ty>(val) {
 						Ok(val) => { $head.push(val $($($accessor)+)?); },
-						Err(err) => break Err($crate::anyhow::anyhow!(concat!("Failed to parse \"{}\" as ", stringify!($head_ty), ": {}"), val, err))
+						Err(err) => break Err(err)
 					}
 				}
 			};
@@ -71,17 +304,62 @@ macro_rules! match_tokens {
 		}
 	};
 
+	// ident-repetition ended by a literal
+	(
+		__internal $iter: expr $(, $partial: expr)*;
+		...$head: ident: Vec<$head_ty: ty> $({ $($accessor: tt)+ })?, $end: literal $(| $ends: literal)*, $($rest: tt)*
+	) => {
+		{
+			let mut $head = Vec::new();
+			let result = loop {
+				match $iter.peek() {
+					None | Some(&($end $(| $ends)*)) => break Ok(()),
+					Some(&val) => {
+						$iter.next();
+						match $iter.parse::<$head_ty>(val) {
+							Ok(val) => { $head.push(val $($($accessor)+)?); },
+							Err(err) => break Err(err)
+						}
+					}
+				}
+			};
+
+			match result {
+				Ok(()) => $crate::match_tokens!(__internal $iter $(, $partial)*, $head; $end $(| $ends)*, $($rest)*),
+				Err(err) => Err(err)
+			}
+		}
+	};
+
+	// ident-optional
+	(
+		__internal $iter: expr $(, $partial: expr)*;
+		$head: ident: Option<$head_ty: ty> $({ $($accessor: tt)+ })?, $($rest: tt)*
+	) => {
+		{
+			let $head = match $iter.peek().map(|&val| <$head_ty>::try_from(val)) {
+				Some(Ok(val)) => {
+					$iter.next();
+					Some(val $($($accessor)+)?)
+				}
+				_ => None
+			};
+
+			$crate::match_tokens!(__internal $iter $(, $partial)*, $head; $($rest)*)
+		}
+	};
+
 	// ident
 	(
 		__internal $iter: expr $(, $partial: expr)*;
 		$head: ident: $head_ty: ty $({ $($accessor: tt)+ })?, $($rest: tt)*
 	) => {
 		match $iter.next() {
-			Some($head) => match <$head_ty>::try_from($head) {
+			Some($head) => match $iter.parse::<$head_ty>($head) {
 				Ok($head) => $crate::match_tokens!(__internal $iter $(, $partial)*, $head $($($accessor)+)?; $($rest)*),
-				Err(err) => Err($crate::anyhow::anyhow!(concat!("Failed to parse \"{}\" as ", stringify!($head_ty), ": {}"), $head, err))
+				Err(err) => Err(err)
 			},
-			res => Err($crate::anyhow::anyhow!(concat!("Expected ", stringify!($head), " but found {:?}"), res))
+			None => Err($iter.error(concat!("Expected ", stringify!($head))))
 		}
 	};
 
@@ -92,4 +370,170 @@ macro_rules! match_tokens {
 	) => {
 		Ok(($($partial),+))
 	};
-}
\ No newline at end of file
+
+	// terminal without captures
+	(
+		__internal $iter: expr;
+		$(,)*
+	) => {
+		Ok::<(), $crate::macros::TokenError>(())
+	};
+}
+
+/// Declares a struct parsed from a single line of tokens with [`match_tokens!`].
+///
+/// The pattern refers to fields by name, `field` is parsed with [`FromStr`] as the field type and `...field` collects
+/// the remaining tokens into a `Vec` field the same way. Literals and bound alternatives are passed to [`match_tokens!`] as they are.
+/// Tokens are produced by splitting the line with the `split` pattern and skipping empty ones.
+///
+/// Besides `TryFrom<&str>` the struct gets a `parse_lines` function which parses each non-empty line of its input.
+/// Errors are [`TokenError`]s with the source line attached, `parse_lines` also attaches the line number.
+/// ```
+/// # use aoc_commons::record;
+/// #
+/// #[derive(Debug, PartialEq)]
+/// enum Direction { Up, Down }
+///
+/// record! {
+///     #[derive(Debug, PartialEq)]
+///     struct Move {
+///         direction: Direction,
+///         count: usize,
+///         labels: Vec<String>
+///     }
+///     split [' ', ','] => "move", direction: ("up" => Direction::Up, "down" => Direction::Down), count, "labels", ...labels
+/// }
+///
+/// let moves = Move::parse_lines("move up 3 labels a, b\n\nmove down 1 labels\n").unwrap();
+/// assert_eq!(moves, vec![
+///     Move { direction: Direction::Up, count: 3, labels: vec!["a".to_string(), "b".to_string()] },
+///     Move { direction: Direction::Down, count: 1, labels: vec![] }
+/// ]);
+///
+/// let err = Move::parse_lines("move up 3 labels\nmove left 1 labels").unwrap_err();
+/// assert_eq!(err.to_string(), "line 2, token 1 \"left\": Expected direction to be one of \"up\" \"down\"");
+/// assert_eq!(err.snippet().unwrap(), "  move left 1 labels\n       ^^^^");
+/// ```
+#[macro_export]
+macro_rules! record {
+	(
+		$(#[$meta: meta])*
+		$vis: vis struct $name: ident {
+			$($field_vis: vis $field: ident: $field_ty: ty),+ $(,)?
+		}
+		split $split: expr => $($pattern: tt)+
+	) => {
+		$(#[$meta])*
+		$vis struct $name {
+			$($field_vis $field: $field_ty),+
+		}
+		impl<'a> TryFrom<&'a str> for $name {
+			type Error = $crate::macros::TokenError;
+
+			fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+				$crate::record!(__pattern value, $split; []; []; $($pattern)+ ,).map_err(|err: $crate::macros::TokenError| err.in_line(value))
+			}
+		}
+		impl $name {
+			/// Parses each non-empty line of `input`.
+			#[allow(dead_code)]
+			$vis fn parse_lines(input: &str) -> Result<Vec<Self>, $crate::macros::TokenError> {
+				input.lines().enumerate().filter(|(_, line)| !line.is_empty()).map(
+					|(number, line)| Self::try_from(line).map_err(|err| err.at_line_number(number + 1))
+				).collect()
+			}
+		}
+	};
+
+	// literal
+	(
+		__pattern $value: expr, $split: expr; [$($bindings: ident),*]; [$($tokens: tt)*];
+		$head: literal $(| $heads: literal)*, $($rest: tt)*
+	) => {
+		$crate::record!(__pattern $value, $split; [$($bindings),*]; [$($tokens)* $head $(| $heads)*,]; $($rest)*)
+	};
+
+	// alternatives
+	(
+		__pattern $value: expr, $split: expr; [$($bindings: ident),*]; [$($tokens: tt)*];
+		$head: ident: ( $($alternatives: tt)+ ), $($rest: tt)*
+	) => {
+		$crate::record!(__pattern $value, $split; [$($bindings,)* $head]; [$($tokens)* $head: ( $($alternatives)+ ),]; $($rest)*)
+	};
+
+	// rest
+	(
+		__pattern $value: expr, $split: expr; [$($bindings: ident),*]; [$($tokens: tt)*];
+		...$head: ident $(,)?
+	) => {
+		$crate::record!(
+			__pattern $value, $split; [$($bindings,)* $head]; [$($tokens)* ...$head: Vec<$crate::macros::FromStrToTryFromAdapter<_>> {.0}];
+		)
+	};
+
+	// field
+	(
+		__pattern $value: expr, $split: expr; [$($bindings: ident),*]; [$($tokens: tt)*];
+		$head: ident, $($rest: tt)*
+	) => {
+		$crate::record!(
+			__pattern $value, $split; [$($bindings,)* $head]; [$($tokens)* $head: $crate::macros::FromStrToTryFromAdapter<_> {.0},]; $($rest)*
+		)
+	};
+
+	// terminal
+	(
+		__pattern $value: expr, $split: expr; [$($bindings: ident),+]; [$($tokens: tt)*];
+		$(,)*
+	) => {
+		$crate::match_tokens!($value.split($split).filter(|s| !s.is_empty()); $($tokens)*).map(
+			#[allow(unused_parens)]
+			|($($bindings),+)| Self { $($bindings),+ }
+		)
+	};
+}
+
+#[cfg(test)]
+mod test {
+	use super::{short_type_name, FromStrToTryFromAdapter, TokenError};
+
+	fn parse(line: &str) -> Result<(usize, usize), TokenError> {
+		crate::match_tokens!(
+			line.split(' ').filter(|s| !s.is_empty());
+			"move", count: FromStrToTryFromAdapter<usize> {.0}, "from" | "of", from: FromStrToTryFromAdapter<usize> {.0}
+		)
+	}
+
+	#[test]
+	fn test_token_error() {
+		assert_eq!(parse("move 5 from 3").unwrap(), (5, 3));
+
+		let line = "move 5  to 3";
+		let err = parse(line).unwrap_err().in_line(line).at_line_number(7);
+		assert_eq!((err.index, err.token.as_deref(), err.span.clone()), (2, Some("to"), Some(8 .. 10)));
+		assert_eq!(err.to_string(), "line 7, token 2 \"to\": Expected literal \"from\" or \"of\"");
+		assert_eq!(err.snippet().unwrap(), "  move 5  to 3\n          ^^");
+
+		let line = "move x";
+		let err = parse(line).unwrap_err().in_line(line);
+		assert_eq!(err.to_string(), "token 1 \"x\": Failed to parse as usize: invalid digit found in string");
+
+		let line = "move 5 from";
+		let err = parse(line).unwrap_err().in_line(line);
+		assert_eq!(err.to_string(), "token 3 (end of input): Expected from");
+		assert_eq!(err.snippet().unwrap(), "  move 5 from\n             ^");
+
+		// the token is not part of an unrelated line
+		let other = String::from("move 5 to 3");
+		let err = parse("move 5 to 3").unwrap_err().in_line(&other);
+		assert_eq!(err.span, None);
+		assert_eq!(err.snippet(), None);
+	}
+
+	#[test]
+	fn test_short_type_name() {
+		assert_eq!(short_type_name("aoc_commons::macros::FromStrToTryFromAdapter<usize>"), "usize");
+		assert_eq!(short_type_name("alloc::vec::Vec<core::option::Option<i32>>"), "Vec<Option<i32>>");
+		assert_eq!(short_type_name("&str"), "&str");
+	}
+}
@@ -0,0 +1,374 @@
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/// Primitive integer types, converted through `i128` where intermediate results could overflow.
+///
+/// Constants and methods share their names with the inherent ones of the primitive types and behave the same. On a
+/// concrete type like `isize` the inherent one is picked even with this trait in scope, so no disambiguation is needed,
+/// the trait ones are only used in generic code.
+pub trait Integer: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Rem<Output = Self> {
+	const ZERO: Self;
+	const ONE: Self;
+	const MIN: Self;
+	const MAX: Self;
+
+	fn to_i128(self) -> i128;
+	fn from_i128(value: i128) -> Option<Self>;
+
+	fn checked_add(self, rhs: Self) -> Option<Self>;
+	fn checked_sub(self, rhs: Self) -> Option<Self>;
+	fn checked_mul(self, rhs: Self) -> Option<Self>;
+	fn saturating_add(self, rhs: Self) -> Self;
+	fn saturating_sub(self, rhs: Self) -> Self;
+	fn rem_euclid(self, rhs: Self) -> Self;
+
+	/// Absolute value, identity for unsigned types.
+	fn abs(self) -> Self {
+		if self < Self::ZERO { Self::ZERO - self } else { self }
+	}
+}
+macro_rules! impl_integer {
+	($($ty: ty),+) => {
+		$(
+			impl Integer for $ty {
+				const ZERO: Self = 0;
+				const ONE: Self = 1;
+				const MIN: Self = <$ty>::MIN;
+				const MAX: Self = <$ty>::MAX;
+
+				fn to_i128(self) -> i128 {
+					self as i128
+				}
+
+				fn from_i128(value: i128) -> Option<Self> {
+					Self::try_from(value).ok()
+				}
+
+				fn checked_add(self, rhs: Self) -> Option<Self> {
+					<$ty>::checked_add(self, rhs)
+				}
+
+				fn checked_sub(self, rhs: Self) -> Option<Self> {
+					<$ty>::checked_sub(self, rhs)
+				}
+
+				fn checked_mul(self, rhs: Self) -> Option<Self> {
+					<$ty>::checked_mul(self, rhs)
+				}
+
+				fn saturating_add(self, rhs: Self) -> Self {
+					<$ty>::saturating_add(self, rhs)
+				}
+
+				fn saturating_sub(self, rhs: Self) -> Self {
+					<$ty>::saturating_sub(self, rhs)
+				}
+
+				fn rem_euclid(self, rhs: Self) -> Self {
+					<$ty>::rem_euclid(self, rhs)
+				}
+			}
+		)+
+	};
+}
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+/// Greatest common divisor, always non-negative. `gcd(0, 0) == 0`.
+///
+/// # Panics
+/// When the result does not fit into `T`, which only happens for `gcd(T::MIN, 0)` and `gcd(T::MIN, T::MIN)` of signed
+/// types. See [`checked_gcd`].
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+	checked_gcd(a, b).expect("gcd does not fit into the type")
+}
+
+/// Greatest common divisor, or `None` if it does not fit into `T`.
+pub fn checked_gcd<T: Integer>(a: T, b: T) -> Option<T> {
+	// the remainders keep the sign of the dividend, so only the result needs abs, which lets `T::MIN` through
+	let (mut a, mut b) = (a, b);
+	while b != T::ZERO {
+		// `T::MIN % -1` overflows
+		if b == T::ONE || T::ZERO.checked_sub(T::ONE) == Some(b) {
+			return Some(T::ONE);
+		}
+		(a, b) = (b, a % b);
+	}
+
+	checked_abs(a)
+}
+
+/// Least common multiple, always non-negative. `lcm(x, 0) == 0`.
+///
+/// # Panics
+/// When the result does not fit into `T`. See [`checked_lcm`].
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+	checked_lcm(a, b).expect("lcm does not fit into the type")
+}
+
+/// Least common multiple, or `None` if it does not fit into `T`.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+	if a == T::ZERO || b == T::ZERO {
+		return Some(T::ZERO);
+	}
+
+	checked_abs((a / checked_gcd(a, b)?).checked_mul(b)?)
+}
+
+fn checked_abs<T: Integer>(value: T) -> Option<T> {
+	if value < T::ZERO { T::ZERO.checked_sub(value) } else { Some(value) }
+}
+
+/// Greatest common divisor of all values, `0` for no values.
+///
+/// # Panics
+/// Like [`gcd`].
+pub fn gcd_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+	values.into_iter().fold(T::ZERO, gcd)
+}
+
+/// Least common multiple of all values, `1` for no values.
+///
+/// # Panics
+/// Like [`lcm`].
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+	values.into_iter().fold(T::ONE, lcm)
+}
+
+/// Extended Euclidean algorithm, returns `(g, x, y)` such that `a * x + b * y == g == gcd(a, b)`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+	let (mut old_r, mut r) = (a, b);
+	let (mut old_x, mut x) = (1, 0);
+	let (mut old_y, mut y) = (0, 1);
+
+	while r != 0 {
+		let q = old_r / r;
+		(old_r, r) = (r, old_r - q * r);
+		(old_x, x) = (x, old_x - q * x);
+		(old_y, y) = (y, old_y - q * y);
+	}
+
+	if old_r < 0 {
+		(-old_r, -old_x, -old_y)
+	} else {
+		(old_r, old_x, old_y)
+	}
+}
+
+/// `(a + b) mod m` for `0 <= a, b < m` without overflowing.
+fn add_mod(a: i128, b: i128, m: i128) -> i128 {
+	if a >= m - b { a - (m - b) } else { a + b }
+}
+
+/// `(a * b) mod m` for `a, b >= 0` and `m > 0` without overflowing.
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+	if let Some(product) = a.checked_mul(b) {
+		return product % m;
+	}
+
+	let (mut a, mut b) = (a % m, b % m);
+	let mut result = 0;
+	while b > 0 {
+		if b & 1 == 1 {
+			result = add_mod(result, a, m);
+		}
+		a = add_mod(a, a, m);
+		b >>= 1;
+	}
+
+	result
+}
+
+/// Inverse of `a` modulo `modulus`, if `a` and `modulus` are coprime. The result is in `0 .. modulus`.
+pub fn mod_inverse<T: Integer>(a: T, modulus: T) -> Option<T> {
+	let m = modulus.to_i128();
+	if m <= 0 {
+		return None;
+	}
+
+	let (g, x, _) = extended_gcd(a.to_i128().rem_euclid(m), m);
+	if g != 1 {
+		return None;
+	}
+
+	T::from_i128(x.rem_euclid(m))
+}
+
+/// `base^exp mod modulus`, the result is in `0 .. modulus`.
+///
+/// # Panics
+/// When `modulus` is not positive.
+pub fn mod_pow<T: Integer>(base: T, mut exp: u64, modulus: T) -> T {
+	let m = modulus.to_i128();
+	assert!(m > 0, "modulus must be positive");
+
+	let mut base = base.to_i128().rem_euclid(m);
+	let mut result = 1 % m;
+	while exp > 0 {
+		if exp & 1 == 1 {
+			result = mul_mod(result, base, m);
+		}
+		base = mul_mod(base, base, m);
+		exp >>= 1;
+	}
+
+	T::from_i128(result).unwrap()
+}
+
+/// Chinese remainder theorem - finds `x` such that `x ≡ r (mod m)` for all `(r, m)` pairs.
+///
+/// Moduli do not need to be coprime. Returns `(x, lcm of moduli)` with `x` in `0 .. lcm`,
+/// or `None` if the congruences are inconsistent, a modulus is not positive or the result does not fit into `T`.
+pub fn crt<T: Integer>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+	let mut x: i128 = 0;
+	let mut m: i128 = 1;
+
+	for (residue, modulus) in congruences {
+		let modulus = modulus.to_i128();
+		if modulus <= 0 {
+			return None;
+		}
+		let residue = residue.to_i128().rem_euclid(modulus);
+
+		// x + m * k ≡ residue (mod modulus)
+		let (g, inverse, _) = extended_gcd(m, modulus);
+		let diff = (residue - x).rem_euclid(modulus);
+		if diff % g != 0 {
+			return None;
+		}
+
+		let step = modulus / g;
+		let k = mul_mod(diff / g, inverse.rem_euclid(step), step);
+		// x < m and k < step, so x + m * k < m * step and nothing overflows once the new modulus fits
+		let new_m = m.checked_mul(step)?;
+		x += m * k;
+		m = new_m;
+	}
+
+	Some((T::from_i128(x)?, T::from_i128(m)?))
+}
+
+/// Integer square root, the largest `r` such that `r * r <= n`.
+///
+/// # Panics
+/// When `n` is negative.
+pub fn isqrt<T: Integer>(n: T) -> T {
+	let n = n.to_i128();
+	assert!(n >= 0, "cannot take square root of a negative number");
+
+	T::from_i128((n as u128).isqrt() as i128).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+	use super::{gcd, checked_gcd, lcm, checked_lcm, gcd_all, lcm_all, extended_gcd, mod_inverse, mod_pow, crt, isqrt};
+
+	#[test]
+	fn test_gcd_lcm() {
+		assert_eq!(gcd(12, 18), 6);
+		assert_eq!(gcd(-12, 18), 6);
+		assert_eq!(gcd(0, 7u32), 7);
+		assert_eq!(gcd(0, 0), 0);
+
+		assert_eq!(lcm(4, 6), 12);
+		assert_eq!(lcm(-4, 6), 12);
+		assert_eq!(lcm(0, 6), 0);
+
+		assert_eq!(gcd_all([24, 36, 60]), 12);
+		assert_eq!(lcm_all([23u64, 19, 13, 17]), 96577);
+		assert_eq!(lcm_all([2usize, 3, 4, 6]), 12);
+		assert_eq!(lcm_all(Vec::<u8>::new()), 1);
+	}
+
+	#[test]
+	fn test_gcd_lcm_bounds() {
+		assert_eq!(gcd(i8::MIN, 6), 2);
+		assert_eq!(gcd(i8::MIN, -1), 1);
+		assert_eq!(gcd(-1, i8::MIN), 1);
+		assert_eq!(gcd(i8::MIN, i8::MAX), 1);
+		assert_eq!(gcd(u8::MAX, 255), 255);
+		assert_eq!(checked_gcd(i8::MIN, 0), None);
+		assert_eq!(checked_gcd(i8::MIN, i8::MIN), None);
+		assert_eq!(checked_gcd(i8::MIN, 64), Some(64));
+
+		assert_eq!(checked_lcm(0, -6), Some(0));
+		assert_eq!(checked_lcm(16u8, 17), None);
+		assert_eq!(checked_lcm(15u8, 17), Some(255));
+		assert_eq!(checked_lcm(-64i8, 2), Some(64));
+		for b in [1, 2, -1, -3] {
+			assert_eq!(checked_lcm(i8::MIN, b), None);
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "lcm does not fit")]
+	fn test_lcm_overflow() {
+		lcm_all([16u8, 17]);
+	}
+
+	#[test]
+	fn test_extended_gcd() {
+		for (a, b) in [(240, 46), (-7, 3), (0, 5), (17, 0), (1_000_000_007, 998_244_353)] {
+			let (g, x, y) = extended_gcd(a, b);
+			assert_eq!(g, gcd(a, b));
+			assert_eq!(a * x + b * y, g);
+		}
+	}
+
+	#[test]
+	fn test_mod_inverse() {
+		assert_eq!(mod_inverse(3, 11), Some(4));
+		assert_eq!(mod_inverse(-3, 11), Some(7));
+		assert_eq!(mod_inverse(6, 9), None);
+
+		let m = 1_000_000_007i64;
+		let inverse = mod_inverse(123_456_789, m).unwrap();
+		assert_eq!((123_456_789 * inverse) % m, 1);
+	}
+
+	#[test]
+	fn test_mod_pow() {
+		assert_eq!(mod_pow(2, 10, 1000), 24);
+		assert_eq!(mod_pow(-2, 3, 5), 2);
+		assert_eq!(mod_pow(7, 0, 1), 0);
+		assert_eq!(mod_pow(3u64, 1_000_000_006, 1_000_000_007), 1);
+
+		// intermediate products overflow i128
+		let m = (1i128 << 100) + 277;
+		assert_eq!(mod_pow(m - 1, 2, m), 1);
+
+		// doubling overflows i128 for moduli above i128::MAX / 2
+		assert_eq!(mod_pow(2, 127, i128::MAX), 1);
+		assert_eq!(mod_pow(i128::MAX - 1, 3, i128::MAX), i128::MAX - 1);
+	}
+
+	#[test]
+	fn test_crt() {
+		assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+		assert_eq!(crt([(0u64, 7), (12, 13), (55, 59), (25, 31), (12, 19)]), Some((1068781, 3162341)));
+
+		// non-coprime moduli
+		assert_eq!(crt([(2, 6), (8, 10)]), Some((8, 30)));
+		assert_eq!(crt([(1, 6), (2, 4)]), None);
+
+		// negative residues
+		assert_eq!(crt([(-1, 4), (-1, 6)]), Some((11, 12)));
+
+		// does not fit
+		assert_eq!(crt([(0u8, 251), (1u8, 241)]), None);
+		assert_eq!(crt(Vec::<(i64, i64)>::new()), Some((0, 1)));
+		assert_eq!(crt([(0, 0)]), None::<(i32, i32)>);
+
+		// the moduli multiply up to just below i128::MAX
+		let m = (1i128 << 126) - 1;
+		assert_eq!(crt([(1, 2), (-1, m)]), Some((2 * m - 1, 2 * m)));
+		assert_eq!(crt([(i128::MAX - 1, i128::MAX)]), Some((i128::MAX - 1, i128::MAX)));
+		assert_eq!(crt([(0, 4), (0, m)]), None);
+	}
+
+	#[test]
+	fn test_isqrt() {
+		assert_eq!(isqrt(0), 0);
+		assert_eq!(isqrt(15), 3);
+		assert_eq!(isqrt(16), 4);
+		assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+	}
+}
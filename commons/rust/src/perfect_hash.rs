@@ -35,6 +35,11 @@ impl<T: Eq + Hash> BitHash64Assigner<T> {
 		None
 	}
 }
+impl<T: Eq + Hash> Default for BitHash64Assigner<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
 
 #[derive(Debug)]
 pub struct BitHash64<T>(u64, std::marker::PhantomData<T>);
@@ -53,7 +58,7 @@ impl<T> BitHash64<T> {
 }
 impl<T> Clone for BitHash64<T> {
 	fn clone(&self) -> Self {
-		Self(self.0, std::marker::PhantomData)
+		*self
 	}
 }
 impl<T> Copy for BitHash64<T> {}
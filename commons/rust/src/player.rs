@@ -0,0 +1,356 @@
+use std::{
+	fmt::{self, Write as _},
+	fs::File,
+	io::{self, IsTerminal, Read, Stdout, Write},
+	panic,
+	process::Command,
+	sync::{Mutex, Once},
+	thread,
+	time::{Duration, Instant}
+};
+
+use anyhow::Context;
+
+use crate::{
+	geometry::{Grid2, Point},
+	image::{self, luma, Palette}
+};
+
+const HELP: &str = "space pause, n step, +/- speed, q skip, ^C quit";
+
+/// `^C`, delivered as a key because the terminal is switched to `-isig`.
+const INTERRUPT: u8 = 0x03;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerConfig {
+	/// Frames per second when playing, the player can be sped up or slowed down with keys.
+	pub fps: f64
+}
+
+fn stty(tty: &File, args: &[&str]) -> io::Result<String> {
+	let output = Command::new("stty").args(args).stdin(tty.try_clone()?).output()?;
+	if !output.status.success() {
+		return Err(io::Error::other(format!("stty exited with {}", output.status)));
+	}
+
+	Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Terminal taken over by the player, `Some` while the cursor is hidden.
+///
+/// Kept apart from [`PLAYER`] so that the panic hook can restore the terminal even when the player lock is held.
+static TERMINAL: Mutex<Option<TerminalState>> = Mutex::new(None);
+
+struct TerminalState {
+	/// Settings from `stty -g` when keys are being read.
+	stty: Option<String>
+}
+
+/// Shows the cursor and restores the terminal settings changed by the player, does nothing if they were not changed.
+///
+/// Called when the player stops, from the panic hook and on `^C`.
+fn restore_terminal() {
+	let Some(state) = TERMINAL.lock().unwrap_or_else(|err| err.into_inner()).take() else {
+		return;
+	};
+
+	if let Some(saved) = state.stty {
+		if let Ok(tty) = File::open("/dev/tty") {
+			let _ = stty(&tty, &[&saved]);
+		}
+	}
+	let mut out = io::stdout();
+	let _ = out.write_all(b"\x1b[0m\x1b[?25h").and_then(|_| out.flush());
+}
+
+/// Keys read from the controlling terminal without waiting for enter.
+///
+/// The terminal is put into non-canonical mode with `stty`, also with `-isig` so that `^C` can restore it before exiting.
+struct Keys {
+	tty: File
+}
+impl Keys {
+	fn open() -> io::Result<Self> {
+		let tty = File::open("/dev/tty")?;
+		let saved = stty(&tty, &["-g"])?.trim().to_string();
+		stty(&tty, &["-icanon", "-echo", "-isig", "min", "0", "time", "0"])?;
+		TERMINAL.lock().unwrap_or_else(|err| err.into_inner()).get_or_insert(TerminalState { stty: None }).stty = Some(saved);
+
+		Ok(Self { tty })
+	}
+
+	/// Next pressed key, `None` immediately if there is none.
+	fn read(&mut self) -> Option<u8> {
+		let mut key = [0];
+		match self.tty.read(&mut key) {
+			Ok(1) => Some(key[0]),
+			_ => None
+		}
+	}
+}
+
+/// Shows grid frames one after another.
+///
+/// On a terminal each frame is redrawn in place with cell colours from [`Palette`] and the speed is controlled with keys.
+/// Otherwise frames are written as plain text one after another without any delay.
+pub struct Player<W: Write> {
+	out: W,
+	/// Redraw in place using ANSI escape codes.
+	ansi: bool,
+	/// Take over the controlling terminal on the first frame, only for the player on stdout.
+	own_terminal: bool,
+	keys: Option<Keys>,
+	delay: Duration,
+	paused: bool,
+	/// Set by the skip key, all further frames are ignored.
+	stopped: bool,
+	name: String,
+	index: usize
+}
+impl Player<Stdout> {
+	/// Player on stdout, interactive when stdout is a terminal.
+	pub fn new(config: PlayerConfig) -> Self {
+		let mut player = Player::plain(io::stdout(), config);
+		player.ansi = io::stdout().is_terminal();
+		player.own_terminal = player.ansi;
+
+		player
+	}
+}
+impl<W: Write> Player<W> {
+	/// Player writing plain text frames to `out`.
+	pub fn plain(out: W, config: PlayerConfig) -> Self {
+		Self {
+			out,
+			ansi: false,
+			own_terminal: false,
+			keys: None,
+			delay: Duration::from_secs_f64(1.0 / config.fps),
+			paused: false,
+			stopped: false,
+			name: String::new(),
+			index: 0
+		}
+	}
+
+	pub fn is_interactive(&self) -> bool {
+		self.ansi
+	}
+
+	/// Shows the next frame of the sequence `name` and waits until the next one should be shown.
+	pub fn show<T: Palette + fmt::Display>(&mut self, name: &str, grid: &Grid2<T>) -> anyhow::Result<()> {
+		if self.stopped {
+			return Ok(());
+		}
+
+		if self.own_terminal {
+			self.own_terminal = false;
+			TERMINAL.lock().unwrap_or_else(|err| err.into_inner()).get_or_insert(TerminalState { stty: None });
+			self.keys = Keys::open().map_err(|err| log::warn!("Cannot read keys from terminal: {}", err)).ok();
+		}
+
+		if name != self.name {
+			self.name = name.to_string();
+			self.index = 0;
+		}
+
+		let text = if self.ansi { self.render_ansi(grid) } else { format!("{} #{}:\n{}\n", self.name, self.index, grid) };
+		self.out.write_all(text.as_bytes()).and_then(|_| self.out.flush()).context("Failed to write frame")?;
+		self.index += 1;
+
+		if self.ansi {
+			self.wait()?;
+		}
+
+		Ok(())
+	}
+
+	fn status(&self) -> String {
+		format!(
+			"{} #{} [{:.1} fps{}] {}",
+			self.name, self.index, 1.0 / self.delay.as_secs_f64(), if self.paused { ", paused" } else { "" }, HELP
+		)
+	}
+
+	fn render_ansi<T: Palette + fmt::Display>(&self, grid: &Grid2<T>) -> String {
+		// home, status line, clear the rest of the line
+		let mut text = format!("{}{}\x1b[K\n", if self.index == 0 { "\x1b[?25l\x1b[2J\x1b[H" } else { "\x1b[H" }, self.status());
+
+		for y in grid.y_range() {
+			for x in grid.x_range() {
+				let cell = grid.get(Point::<2>::new(x, y)).unwrap();
+				let [r, g, b] = cell.color();
+				let foreground = if luma([r, g, b]) > 128 { 0 } else { 255 };
+				let _ = write!(text, "\x1b[48;2;{};{};{}m\x1b[38;2;{f};{f};{f}m{}", r, g, b, cell, f = foreground);
+			}
+			text.push_str("\x1b[0m\x1b[K\n");
+		}
+		// clear anything left over from a larger frame
+		text.push_str("\x1b[J");
+
+		text
+	}
+
+	fn handle_key(&mut self, key: u8) -> bool {
+		match key {
+			b' ' => self.paused = !self.paused,
+			b'+' | b'=' => self.delay = (self.delay / 2).max(Duration::from_micros(100)),
+			b'-' => self.delay = (self.delay * 2).min(Duration::from_secs(10)),
+			b'q' => self.stopped = true,
+			// step
+			b'n' | b'.' => return self.paused,
+			_ => ()
+		}
+
+		false
+	}
+
+	/// Waits for the frame delay or, when paused, until a step or resume, handling keys in the meantime.
+	fn wait(&mut self) -> anyhow::Result<()> {
+		const POLL: Duration = Duration::from_millis(10);
+
+		let start = Instant::now();
+		loop {
+			let mut step = false;
+			let mut changed = false;
+			while let Some(key) = self.keys.as_mut().and_then(Keys::read) {
+				if key == INTERRUPT {
+					restore_terminal();
+					std::process::exit(130);
+				}
+
+				step |= self.handle_key(key);
+				changed = true;
+			}
+			if self.stopped {
+				// the rest runs without frames, give the terminal back
+				self.keys = None;
+				restore_terminal();
+				return Ok(());
+			}
+			if changed {
+				let status = format!("\x1b[s\x1b[H{}\x1b[K\x1b[u", self.status());
+				self.out.write_all(status.as_bytes()).and_then(|_| self.out.flush()).context("Failed to write status")?;
+			}
+
+			let elapsed = start.elapsed();
+			if step || (!self.paused && elapsed >= self.delay) {
+				return Ok(());
+			}
+
+			thread::sleep(if self.paused { POLL } else { POLL.min(self.delay - elapsed) });
+		}
+	}
+}
+
+static PLAYER: Mutex<Option<Player<Stdout>>> = Mutex::new(None);
+
+/// Stops the process-wide player and restores the terminal when dropped.
+#[must_use = "the player stops when the guard is dropped"]
+pub struct PlayerGuard(());
+impl Drop for PlayerGuard {
+	fn drop(&mut self) {
+		*PLAYER.lock().unwrap_or_else(|err| err.into_inner()) = None;
+		restore_terminal();
+	}
+}
+
+/// Starts the process-wide player used by [`frame`], it runs until the returned guard is dropped.
+///
+/// A panic hook restores the terminal too, in case the guard is not dropped because the panic aborts.
+pub fn start_player(config: PlayerConfig) -> PlayerGuard {
+	static HOOK: Once = Once::new();
+	HOOK.call_once(|| {
+		let previous = panic::take_hook();
+		panic::set_hook(Box::new(move |info| {
+			restore_terminal();
+			previous(info);
+		}));
+	});
+
+	*PLAYER.lock().unwrap_or_else(|err| err.into_inner()) = Some(Player::new(config));
+
+	PlayerGuard(())
+}
+
+/// Whether [`frame`] records or shows anything, so that simulations can skip building grids for it otherwise.
+pub fn frames_enabled() -> bool {
+	image::frames_enabled() || PLAYER.lock().unwrap_or_else(|err| err.into_inner()).as_ref().is_some_and(|player| !player.stopped)
+}
+
+/// Frame callback of grid simulations, records the grid with [`image::record_grid`] and shows it in the player.
+///
+/// Both do nothing unless enabled from the command line.
+pub fn frame<T: Palette + fmt::Display>(name: &str, grid: &Grid2<T>) -> anyhow::Result<()> {
+	image::record_grid(name, grid)?;
+
+	match PLAYER.lock().unwrap_or_else(|err| err.into_inner()).as_mut() {
+		None => Ok(()),
+		Some(player) => player.show(name, grid)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::time::Duration;
+
+	use crate::geometry::Grid2;
+
+	use super::{Player, PlayerConfig};
+
+	const CONFIG: PlayerConfig = PlayerConfig { fps: 10.0 };
+
+	#[test]
+	fn test_plain() {
+		let grid = Grid2::new_width(vec![true, false, false, true], 2).unwrap();
+
+		let mut player = Player::plain(Vec::new(), CONFIG);
+		assert!(!player.is_interactive());
+		player.show("a", &grid).unwrap();
+		player.show("a", &grid).unwrap();
+		player.show("b", &grid).unwrap();
+
+		let text = String::from_utf8(std::mem::take(&mut player.out)).unwrap();
+		assert_eq!(text, "a #0:\ntruefalse\nfalsetrue\n\na #1:\ntruefalse\nfalsetrue\n\nb #0:\ntruefalse\nfalsetrue\n\n");
+	}
+
+	#[test]
+	fn test_ansi() {
+		let grid = Grid2::new_width(vec![true, false], 2).unwrap();
+
+		let mut player = Player::plain(Vec::new(), PlayerConfig { fps: 1000.0 });
+		player.ansi = true;
+		player.show("a", &grid).unwrap();
+		player.show("a", &grid).unwrap();
+
+		let text = String::from_utf8(std::mem::take(&mut player.out)).unwrap();
+		let (first, second) = text.split_at(text.rfind("\x1b[H").unwrap());
+		assert!(first.starts_with("\x1b[?25l\x1b[2J\x1b[Ha #0 [1000.0 fps]"));
+		assert!(second.starts_with("\x1b[Ha #1"));
+		assert!(second.contains("\x1b[48;2;255;255;255m\x1b[38;2;0;0;0mtrue\x1b[48;2;0;0;0m\x1b[38;2;255;255;255mfalse\x1b[0m\x1b[K\n"));
+		assert!(second.ends_with("\x1b[J"));
+	}
+
+	#[test]
+	fn test_keys() {
+		let grid = Grid2::new_width(vec![true], 1).unwrap();
+		let mut player = Player::plain(Vec::new(), CONFIG);
+
+		assert!(!player.handle_key(b'n'));
+		player.handle_key(b' ');
+		assert!(player.paused);
+		assert!(player.handle_key(b'n'));
+		player.handle_key(b' ');
+		assert!(!player.paused);
+
+		player.handle_key(b'+');
+		assert_eq!(player.delay, Duration::from_millis(50));
+		player.handle_key(b'-');
+		player.handle_key(b'-');
+		assert_eq!(player.delay, Duration::from_millis(200));
+
+		player.handle_key(b'q');
+		player.show("a", &grid).unwrap();
+		assert!(player.out.is_empty());
+	}
+}
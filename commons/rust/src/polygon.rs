@@ -0,0 +1,221 @@
+use anyhow::Context;
+
+use crate::{
+	geometry::{Point, Grid2},
+	numeric::gcd
+};
+
+type Point2 = Point<2>;
+
+/// Edges of the polygon with `vertices` in order, including the one from the last vertex back to the first.
+fn edges(vertices: &[Point2]) -> impl Iterator<Item = (Point2, Point2)> + '_ {
+	vertices.iter().zip(vertices.iter().cycle().skip(1)).map(|(&a, &b)| (a, b))
+}
+
+/// Twice the signed area (shoelace formula), positive when the vertices turn from +x towards +y.
+///
+/// The area of a lattice polygon is a multiple of one half, so doubling it keeps it exact.
+pub fn signed_area_doubled(vertices: &[Point2]) -> isize {
+	edges(vertices).map(|(a, b)| a.perp_dot(b)).sum()
+}
+
+/// Number of lattice points on the boundary of the polygon.
+pub fn boundary_points(vertices: &[Point2]) -> isize {
+	edges(vertices).map(|(a, b)| { let d = b - a; gcd(d.x(), d.y()) }).sum()
+}
+
+/// Number of lattice points strictly inside of the polygon, by Pick's theorem `A = I + B / 2 - 1`.
+///
+/// The polygon must be simple (not self-intersecting).
+pub fn interior_points(vertices: &[Point2]) -> isize {
+	if vertices.len() < 3 {
+		return 0;
+	}
+
+	(signed_area_doubled(vertices).abs() - boundary_points(vertices) + 2) / 2
+}
+
+/// Number of lattice points inside of or on the boundary of the polygon, that is the number of grid cells it covers.
+pub fn covered_points(vertices: &[Point2]) -> isize {
+	interior_points(vertices) + boundary_points(vertices)
+}
+
+/// Whether `point` is inside of the polygon or on its boundary.
+pub fn contains(vertices: &[Point2], point: Point2) -> bool {
+	let mut inside = false;
+	for (a, b) in edges(vertices) {
+		let cross = (b - a).perp_dot(point - a);
+		if cross == 0 && a.min(b).min(point) == a.min(b) && a.max(b).max(point) == a.max(b) {
+			return true;
+		}
+
+		// edge crosses the ray going from point towards +x
+		if (a.y() > point.y()) != (b.y() > point.y()) && (cross > 0) == (b.y() > a.y()) {
+			inside = !inside;
+		}
+	}
+
+	inside
+}
+
+/// Points of the segment from `from` to `to` (both inclusive) rasterized using Bresenham's algorithm.
+#[derive(Debug, Clone)]
+pub struct LinePoints {
+	at: Option<Point2>,
+	end: Point2,
+	step: Point2,
+	/// `(|dx|, -|dy|)`
+	delta: Point2,
+	error: isize
+}
+impl LinePoints {
+	pub fn new(from: Point2, to: Point2) -> Self {
+		let d = to - from;
+		let delta = Point2::new(d.x().abs(), -d.y().abs());
+
+		Self {
+			at: Some(from),
+			end: to,
+			step: Point2::new(d.x().signum(), d.y().signum()),
+			delta,
+			error: delta.x() + delta.y()
+		}
+	}
+}
+impl Iterator for LinePoints {
+	type Item = Point2;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let result = self.at?;
+		if result == self.end {
+			self.at = None;
+			return Some(result);
+		}
+
+		let mut next = result;
+		let error2 = 2 * self.error;
+		if error2 >= self.delta.y() {
+			self.error += self.delta.y();
+			next += Point2::new(self.step.x(), 0);
+		}
+		if error2 <= self.delta.x() {
+			self.error += self.delta.x();
+			next += Point2::new(0, self.step.y());
+		}
+		self.at = Some(next);
+
+		Some(result)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.at.map(|at| (self.end - at).length_chebyshev() as usize + 1).unwrap_or(0);
+		(len, Some(len))
+	}
+}
+impl ExactSizeIterator for LinePoints {}
+
+pub fn line_points(from: Point2, to: Point2) -> LinePoints {
+	LinePoints::new(from, to)
+}
+
+/// Points of the open path through `points`, each joint is only visited once.
+pub fn polyline_points(points: &[Point2]) -> impl Iterator<Item = Point2> + '_ {
+	points.first().copied().into_iter().chain(
+		points.windows(2).flat_map(|w| line_points(w[0], w[1]).skip(1))
+	)
+}
+
+/// Sets all cells of `grid` on the path through `points` to `value`.
+pub fn fill_polyline<T: Clone>(grid: &mut Grid2<T>, points: &[Point2], value: T) -> anyhow::Result<()> {
+	for point in polyline_points(points) {
+		*grid.get_mut(point).with_context(|| format!("Point {} is outside of the grid", point))? = value.clone();
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use crate::geometry::{Point, Grid2};
+
+	use super::{signed_area_doubled, boundary_points, interior_points, covered_points, contains, line_points, polyline_points, fill_polyline};
+
+	type Point2 = Point<2>;
+
+	#[test]
+	fn test_area() {
+		let rectangle = [Point2::new(0, 0), Point2::new(4, 0), Point2::new(4, 3), Point2::new(0, 3)];
+		assert_eq!(signed_area_doubled(&rectangle), 24);
+		assert_eq!(boundary_points(&rectangle), 14);
+		assert_eq!(interior_points(&rectangle), 6);
+		assert_eq!(covered_points(&rectangle), 20);
+
+		let mut reversed = rectangle;
+		reversed.reverse();
+		assert_eq!(signed_area_doubled(&reversed), -24);
+		assert_eq!(interior_points(&reversed), 6);
+
+		let triangle = [Point2::new(0, 0), Point2::new(3, 1), Point2::new(1, 2)];
+		assert_eq!(signed_area_doubled(&triangle), 5);
+		assert_eq!(boundary_points(&triangle), 3);
+		assert_eq!(interior_points(&triangle), 2);
+
+		assert_eq!(interior_points(&[Point2::new(0, 0), Point2::new(5, 5)]), 0);
+		assert_eq!(signed_area_doubled(&[]), 0);
+	}
+
+	#[test]
+	fn test_contains() {
+		// L shape
+		let polygon = [
+			Point2::new(0, 0), Point2::new(4, 0), Point2::new(4, 2),
+			Point2::new(2, 2), Point2::new(2, 4), Point2::new(0, 4)
+		];
+
+		assert!(contains(&polygon, Point2::new(1, 1)));
+		assert!(contains(&polygon, Point2::new(1, 3)));
+		assert!(!contains(&polygon, Point2::new(3, 3)));
+		assert!(!contains(&polygon, Point2::new(5, 1)));
+		assert!(!contains(&polygon, Point2::new(-1, 2)));
+		// boundary and vertices
+		assert!(contains(&polygon, Point2::new(3, 2)));
+		assert!(contains(&polygon, Point2::new(4, 0)));
+		assert!(contains(&polygon, Point2::new(0, 4)));
+		// ray passes through vertices
+		assert!(!contains(&polygon, Point2::new(-1, 0)));
+		assert!(!contains(&polygon, Point2::new(1, 5)));
+
+		let inside = (-1 ..= 5).flat_map(|y| (-1 ..= 5).map(move |x| Point2::new(x, y))).filter(|p| contains(&polygon, *p)).count();
+		assert_eq!(inside as isize, covered_points(&polygon));
+	}
+
+	#[test]
+	fn test_lines() {
+		assert_eq!(
+			line_points(Point2::new(0, 0), Point2::new(5, 2)).collect::<Vec<_>>(),
+			vec![Point2::new(0, 0), Point2::new(1, 0), Point2::new(2, 1), Point2::new(3, 1), Point2::new(4, 2), Point2::new(5, 2)]
+		);
+		assert_eq!(
+			line_points(Point2::new(2, 3), Point2::new(2, 0)).collect::<Vec<_>>(),
+			vec![Point2::new(2, 3), Point2::new(2, 2), Point2::new(2, 1), Point2::new(2, 0)]
+		);
+		assert_eq!(line_points(Point2::new(1, 1), Point2::new(1, 1)).collect::<Vec<_>>(), vec![Point2::new(1, 1)]);
+
+		for to in [Point2::new(-7, 3), Point2::new(2, -9), Point2::new(-5, -5), Point2::new(6, 1)] {
+			let points: Vec<Point2> = line_points(Point2::ZERO, to).collect();
+			assert_eq!(points.len(), line_points(Point2::ZERO, to).len());
+			assert_eq!(points.len() as isize, to.length_chebyshev() + 1);
+			assert_eq!(points.last(), Some(&to));
+			assert!(points.windows(2).all(|w| (w[1] - w[0]).length_chebyshev() == 1));
+		}
+
+		let path = [Point2::new(0, 0), Point2::new(2, 0), Point2::new(2, 2)];
+		assert_eq!(polyline_points(&path).count(), 5);
+		assert_eq!(polyline_points(&[]).count(), 0);
+
+		let mut grid = Grid2::new_width(vec!['.'; 9], 3).unwrap();
+		fill_polyline(&mut grid, &path, '#').unwrap();
+		assert_eq!(grid.to_string(), "###\n..#\n..#\n");
+		assert!(fill_polyline(&mut grid, &[Point2::new(0, 0), Point2::new(0, 3)], '#').is_err());
+	}
+}
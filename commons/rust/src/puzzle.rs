@@ -0,0 +1,557 @@
+use std::{collections::BTreeMap, fmt, panic, path::Path, str::FromStr};
+
+use anyhow::Context;
+
+use crate::{bench::{self, Sample, Stats}, image, macros::TokenError, player, parse_cli_from, setup_logger, Cli};
+
+/// Solution of a single day, split into parsing the input and solving each part from the parsed input.
+///
+/// The parsed input may borrow from the input text.
+pub trait Puzzle {
+	const YEAR: u16;
+	const DAY: u8;
+
+	type Input<'a>;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input<'_>>;
+
+	/// Parses the input of puzzles whose text does not say everything, e.g. when the example uses a smaller area than
+	/// the real input. Ignores the parameters by default.
+	fn parse_with<'a>(input: &'a str, parameters: &Parameters) -> anyhow::Result<Self::Input<'a>> {
+		let _ = parameters;
+		Self::parse(input)
+	}
+
+	fn part1(input: &Self::Input<'_>) -> anyhow::Result<impl fmt::Display>;
+	fn part2(input: &Self::Input<'_>) -> anyhow::Result<impl fmt::Display>;
+}
+
+/// Named values passed to [`Puzzle::parse_with`], set by `--param NAME=VALUE` or in the table of expected answers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Parameters(BTreeMap<String, String>);
+impl Parameters {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn set(&mut self, name: &str, value: &str) {
+		self.0.insert(name.to_string(), value.to_string());
+	}
+
+	/// Parses `NAME=VALUE` and sets it.
+	pub fn set_assignment(&mut self, assignment: &str) -> anyhow::Result<()> {
+		let (name, value) = assignment.split_once('=').filter(|(name, _)| !name.is_empty())
+			.with_context(|| format!("Expected NAME=VALUE, got \"{}\"", assignment))?;
+		self.set(name, value);
+
+		Ok(())
+	}
+
+	/// Parses the value of `name`, or returns `default` if it is not set.
+	pub fn get_or<T>(&self, name: &str, default: T) -> anyhow::Result<T> where T: FromStr, T::Err: std::error::Error + Send + Sync + 'static {
+		match self.0.get(name) {
+			None => Ok(default),
+			Some(value) => value.parse().with_context(|| format!("Invalid value of parameter {}: {}", name, value))
+		}
+	}
+}
+
+/// Answers of a solved puzzle, `None` for parts which were not run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+	pub part1: Option<String>,
+	pub part2: Option<String>
+}
+impl Answers {
+	pub fn get(&self, part: u8) -> Option<&str> {
+		match part {
+			1 => self.part1.as_deref(),
+			2 => self.part2.as_deref(),
+			_ => None
+		}
+	}
+}
+impl fmt::Display for Answers {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for (part, answer) in [(1, &self.part1), (2, &self.part2)] {
+			match answer {
+				None => (),
+				// multiline answers (e.g. rendered letters) start on their own line
+				Some(answer) if answer.contains('\n') => writeln!(f, "Part {}:\n{}", part, answer.trim_end())?,
+				Some(answer) => writeln!(f, "Part {}: {}", part, answer)?
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Time of each phase of a single solve, `None` for parts which were not run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+	pub parse: Sample,
+	pub part1: Option<Sample>,
+	pub part2: Option<Sample>
+}
+impl Timings {
+	pub fn phases(&self) -> [(&'static str, Option<Sample>); 3] {
+		[("parse", Some(self.parse)), ("part1", self.part1), ("part2", self.part2)]
+	}
+}
+impl fmt::Display for Timings {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut first = true;
+		for (phase, sample) in self.phases() {
+			if let Some(sample) = sample {
+				write!(f, "{}{} {}", if first { "" } else { ", " }, phase, bench::DisplaySeconds(sample.wall.as_secs_f64()))?;
+				first = false;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+fn solve<P: Puzzle>(input: &str, part: Option<u8>, parameters: &Parameters) -> anyhow::Result<(Answers, Timings)> {
+	let (input, parse) = bench::measure(|| P::parse_with(input, parameters));
+	let input = input.context("Failed to parse input")?;
+
+	let mut answers = Answers::default();
+	let mut timings = Timings { parse, ..Timings::default() };
+	if part.unwrap_or(1) == 1 {
+		let (answer, sample) = bench::measure(|| P::part1(&input).map(|a| a.to_string()));
+		answers.part1 = Some(answer.context("Failed to solve part 1")?);
+		timings.part1 = Some(sample);
+	}
+	if part.unwrap_or(2) == 2 {
+		let (answer, sample) = bench::measure(|| P::part2(&input).map(|a| a.to_string()));
+		answers.part2 = Some(answer.context("Failed to solve part 2")?);
+		timings.part2 = Some(sample);
+	}
+
+	Ok((answers, timings))
+}
+
+type SolveFn = fn(&str, Option<u8>, &Parameters) -> anyhow::Result<(Answers, Timings)>;
+
+/// Type-erased [`Puzzle`] stored in a [`Registry`].
+#[derive(Clone, Copy)]
+pub struct Entry {
+	pub year: u16,
+	pub day: u8,
+	solve: SolveFn
+}
+impl Entry {
+	/// Parses `input` and solves the selected part, both when `None`.
+	pub fn solve(&self, input: &str, part: Option<u8>) -> anyhow::Result<Answers> {
+		self.solve_timed(input, part, &Parameters::new()).map(|(answers, _)| answers)
+	}
+
+	/// Same as [`Entry::solve`] with parameters for [`Puzzle::parse_with`], also returning how long each phase took.
+	pub fn solve_timed(&self, input: &str, part: Option<u8>, parameters: &Parameters) -> anyhow::Result<(Answers, Timings)> {
+		(self.solve)(input, part, parameters)
+	}
+}
+impl fmt::Debug for Entry {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Entry").field("year", &self.year).field("day", &self.day).finish()
+	}
+}
+
+/// Puzzles of one or more years, ordered by year and day.
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+	entries: BTreeMap<(u16, u8), Entry>
+}
+impl Registry {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn register<P: Puzzle>(&mut self) -> &mut Self {
+		self.entries.insert((P::YEAR, P::DAY), Entry { year: P::YEAR, day: P::DAY, solve: solve::<P> });
+
+		self
+	}
+
+	pub fn get(&self, year: u16, day: u8) -> Option<&Entry> {
+		self.entries.get(&(year, day))
+	}
+
+	pub fn year(&self, year: u16) -> impl Iterator<Item = &Entry> {
+		self.entries.range((year, 0) ..= (year, u8::MAX)).map(|(_, entry)| entry)
+	}
+
+	pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+		self.entries.values()
+	}
+}
+
+/// How an expected answer is checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectedKind {
+	Normal,
+	/// Only checked when slow answers are requested.
+	Slow,
+	/// Never checked, e.g. because the solution does not terminate on the example.
+	Skip
+}
+
+/// Expected answer of one part of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+	pub day: u8,
+	pub part: u8,
+	pub answer: String,
+	pub kind: ExpectedKind,
+	pub parameters: Parameters
+}
+
+/// Parses a table of expected answers.
+///
+/// Each line is `DAY PART ANSWER [slow|skip] [NAME=VALUE]...`, empty lines and lines starting with `#` are ignored.
+/// A multiline answer is written as `|` followed by its lines, each prefixed with `| `.
+pub fn parse_expected(table: &str) -> anyhow::Result<Vec<Expected>> {
+	let mut result: Vec<Expected> = Vec::new();
+
+	for (number, line) in table.lines().enumerate() {
+		let line = line.trim_end();
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+
+		if let Some(rest) = line.strip_prefix('|') {
+			let last = result.last_mut().filter(|e| e.answer.is_empty() || e.answer.ends_with('\n'));
+			let last = last.with_context(|| format!("Line {}: continuation without a multiline answer", number + 1))?;
+			last.answer.push_str(rest.strip_prefix(' ').unwrap_or(rest));
+			last.answer.push('\n');
+			continue;
+		}
+
+		let mut tokens = line.split_whitespace().peekable();
+		let syntax = || format!("Line {}: expected DAY PART ANSWER [slow|skip] [NAME=VALUE]...", number + 1);
+		let (Some(day), Some(part), Some(answer)) = (tokens.next(), tokens.next(), tokens.next()) else {
+			anyhow::bail!(syntax());
+		};
+		let kind = match tokens.next_if(|token| !token.contains('=')) {
+			None => ExpectedKind::Normal,
+			Some("slow") => ExpectedKind::Slow,
+			Some("skip") => ExpectedKind::Skip,
+			Some(_) => anyhow::bail!(syntax())
+		};
+		let mut parameters = Parameters::new();
+		for token in tokens {
+			parameters.set_assignment(token).with_context(syntax)?;
+		}
+
+		result.push(Expected {
+			day: day.parse().with_context(|| format!("Line {}: invalid day", number + 1))?,
+			part: part.parse().with_context(|| format!("Line {}: invalid part", number + 1))?,
+			answer: if answer == "|" { String::new() } else { answer.to_string() },
+			kind,
+			parameters
+		});
+	}
+
+	Ok(result)
+}
+
+/// Solves each expected answer of `year` on `dir/inputNN.txt` and returns a description of each mismatch.
+///
+/// Panicking solutions are reported as mismatches too.
+pub fn check_expected(
+	registry: &Registry,
+	year: u16,
+	dir: &Path,
+	expected: &[Expected],
+	slow: bool
+) -> Vec<String> {
+	let mut mismatches = Vec::new();
+
+	for expected in expected {
+		let checked = match expected.kind {
+			ExpectedKind::Normal => true,
+			ExpectedKind::Slow => slow,
+			ExpectedKind::Skip => false
+		};
+		if !checked {
+			log::info!("Skipping {} day {:02} part {}", year, expected.day, expected.part);
+			continue;
+		}
+
+		let name = format!("{} day {:02} part {}", year, expected.day, expected.part);
+		let Some(entry) = registry.get(year, expected.day) else {
+			mismatches.push(format!("{}: not registered", name));
+			continue;
+		};
+		let path = dir.join(format!("input{:02}.txt", expected.day));
+		let input = match std::fs::read_to_string(&path) {
+			Ok(input) => input,
+			Err(err) => {
+				mismatches.push(format!("{}: failed to read {}: {}", name, path.display(), err));
+				continue;
+			}
+		};
+
+		match panic::catch_unwind(|| entry.solve_timed(&input, Some(expected.part), &expected.parameters).map(|(answers, _)| answers)) {
+			Err(_) => mismatches.push(format!("{}: panicked", name)),
+			Ok(Err(err)) => mismatches.push(format!("{}: {}", name, report(&err))),
+			Ok(Ok(answers)) => {
+				let answer = answers.get(expected.part).unwrap_or_default();
+				if answer.trim_end() != expected.answer.trim_end() {
+					mismatches.push(format!("{}: expected {:?}, got {:?}", name, expected.answer.trim_end(), answer.trim_end()));
+				}
+			}
+		}
+	}
+
+	mismatches
+}
+
+const USAGE: &str = "Usage: aoc YEAR [DAY|all] [OPTIONS] [INPUT|-]...
+Runs a single day or all days of YEAR, inputs default to inputs/inputNN.txt.";
+
+/// Entry point of a dispatcher binary, runs the days selected by the command line.
+pub fn main(registry: &Registry) -> anyhow::Result<()> {
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	let (year, day, rest) = parse_selection(&args)?;
+
+	let entries: Vec<&Entry> = match day {
+		Some(day) => vec![registry.get(year, day).with_context(|| format!("Day {} of {} is not registered", day, year))?],
+		None => registry.year(year).collect()
+	};
+	anyhow::ensure!(!entries.is_empty(), "No days of {} are registered", year);
+
+	let clis = entries.iter().map(
+		|entry| parse_cli_from(&format!("puzzle{:02}", entry.day), rest.iter().cloned())
+	).collect::<anyhow::Result<Vec<Cli>>>().context("Failed to parse CLI")?;
+	// default inputs differ between days, explicit ones are the same
+	if let [first, second, ..] = clis.as_slice() {
+		anyhow::ensure!(
+			!first.inputs.iter().any(|input| second.inputs.contains(input)),
+			"Explicit inputs cannot be used when running all days"
+		);
+	}
+	setup_logger(&clis[0].log).context("Failed to set up logger")?;
+	image::set_frame_config(clis[0].frames.clone())?;
+	// restores the terminal when dropped, also on errors
+	let _player = clis[0].play.map(player::start_player);
+
+	let mut failed = 0;
+	for (entry, cli) in entries.iter().zip(clis.iter()) {
+		if entries.len() > 1 {
+			println!("=== {} day {:02} ===", entry.year, entry.day);
+		}
+
+		for input in cli.inputs.iter() {
+			if cli.inputs.len() > 1 {
+				println!("== {} ==", input);
+			}
+
+			// keep running the remaining inputs and days
+			if let Err(err) = input.read_to_string().and_then(|text| run_entry(entry, cli, &text)) {
+				failed += 1;
+				eprintln!("Error: {}", report(&err.context(format!("Failed to solve {}", input))));
+			}
+		}
+	}
+	anyhow::ensure!(failed == 0, "{} inputs failed", failed);
+
+	Ok(())
+}
+
+/// Formats the error chain on one line, followed by the snippets of [`TokenError`]s in the chain.
+fn report(err: &anyhow::Error) -> String {
+	let mut result = format!("{:#}", err);
+	for snippet in err.chain().filter_map(|e| e.downcast_ref::<TokenError>()).filter_map(TokenError::snippet) {
+		result.push('\n');
+		result.push_str(&snippet);
+	}
+
+	result
+}
+
+/// Solves `input` once or `--bench` times and prints the answers and timings.
+fn run_entry(entry: &Entry, cli: &Cli, input: &str) -> anyhow::Result<()> {
+	let runs = (0 .. cli.bench.runs.max(1)).map(|_| entry.solve_timed(input, cli.part, &cli.parameters)).collect::<anyhow::Result<Vec<_>>>()?;
+
+	let (answers, timings) = &runs[0];
+	print!("{}", answers);
+	if cli.bench.time {
+		println!("Time: {}", timings);
+	}
+
+	if cli.bench.runs > 0 {
+		let mut rows = Vec::new();
+		for (index, (phase, _)) in timings.phases().into_iter().enumerate() {
+			let samples: Vec<Sample> = runs.iter().filter_map(|(_, timings)| timings.phases()[index].1).collect();
+			if let Some(stats) = Stats::from_samples(&samples) {
+				println!("Bench {}: {}", phase, stats);
+				rows.push((format!("puzzle{:02}_{}", entry.day, phase), stats));
+			}
+		}
+
+		if let Some(path) = cli.bench.csv.as_ref() {
+			bench::append_csv(path, rows.iter().map(|(command, stats)| (command.as_str(), stats)))?;
+		}
+	}
+
+	Ok(())
+}
+
+/// Splits `YEAR [DAY|all]` from the remaining arguments.
+fn parse_selection(args: &[String]) -> anyhow::Result<(u16, Option<u8>, &[String])> {
+	let year = args.first().context(USAGE)?;
+	let year = year.parse().map_err(|_| anyhow::anyhow!("Invalid year: {}\n{}", year, USAGE))?;
+
+	match args.get(1).map(String::as_str) {
+		Some("all") => Ok((year, None, &args[2 ..])),
+		Some(day) if day.chars().all(|c| c.is_ascii_digit()) => {
+			let day = day.parse().map_err(|_| anyhow::anyhow!("Invalid day: {}", day))?;
+			Ok((year, Some(day), &args[2 ..]))
+		}
+		_ => Ok((year, None, &args[1 ..]))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use anyhow::Context;
+
+	use crate::macros::FromStrToTryFromAdapter;
+
+	use super::{check_expected, parse_expected, parse_selection, report, Answers, Expected, ExpectedKind, Parameters, Puzzle, Registry};
+
+	struct Sum;
+	impl Puzzle for Sum {
+		const YEAR: u16 = 2000;
+		const DAY: u8 = 1;
+
+		type Input<'a> = Vec<u32>;
+
+		fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+			Self::parse_with(input, &Parameters::new())
+		}
+
+		fn parse_with<'a>(input: &'a str, parameters: &Parameters) -> anyhow::Result<Self::Input<'a>> {
+			let factor: u32 = parameters.get_or("factor", 1)?;
+			input.lines().map(|l| Ok(l.parse::<u32>()? * factor)).collect()
+		}
+
+		fn part1(input: &Self::Input<'_>) -> anyhow::Result<impl std::fmt::Display> {
+			Ok(input.iter().sum::<u32>())
+		}
+
+		fn part2(input: &Self::Input<'_>) -> anyhow::Result<impl std::fmt::Display> {
+			input.iter().max().copied().ok_or_else(|| anyhow::anyhow!("Empty input"))
+		}
+	}
+
+	#[test]
+	fn test_registry() {
+		let mut registry = Registry::new();
+		registry.register::<Sum>();
+
+		let entry = registry.get(2000, 1).unwrap();
+		assert_eq!(
+			entry.solve("1\n5\n3\n", None).unwrap(),
+			Answers { part1: Some("9".to_string()), part2: Some("5".to_string()) }
+		);
+		assert_eq!(entry.solve("1\n5\n3\n", Some(2)).unwrap().get(1), None);
+		assert!(entry.solve("1\nx\n", None).is_err());
+		assert!(entry.solve("", Some(2)).is_err());
+
+		assert_eq!(registry.year(2000).count(), 1);
+		assert_eq!(registry.year(2001).count(), 0);
+		assert!(registry.get(2000, 2).is_none());
+
+		let (_, timings) = entry.solve_timed("1\n5\n3\n", Some(1), &Parameters::new()).unwrap();
+		assert!(timings.part1.is_some() && timings.part2.is_none());
+		assert!(timings.to_string().starts_with("parse "));
+	}
+
+	#[test]
+	fn test_parameters() {
+		let mut parameters = Parameters::new();
+		parameters.set_assignment("factor=3").unwrap();
+		parameters.set_assignment("name=a=b").unwrap();
+		assert_eq!(parameters.get_or("factor", 1).unwrap(), 3);
+		assert_eq!(parameters.get_or("name", String::new()).unwrap(), "a=b");
+		assert_eq!(parameters.get_or("missing", 7).unwrap(), 7);
+		assert!(parameters.get_or("name", 0).is_err());
+		assert!(parameters.set_assignment("factor").is_err());
+		assert!(parameters.set_assignment("=3").is_err());
+
+		let mut registry = Registry::new();
+		registry.register::<Sum>();
+		let (answers, _) = registry.get(2000, 1).unwrap().solve_timed("1\n5\n3\n", None, &parameters).unwrap();
+		assert_eq!(answers, Answers { part1: Some("27".to_string()), part2: Some("15".to_string()) });
+	}
+
+	#[test]
+	fn test_expected() {
+		let expected = parse_expected("# comment\n\n01 1 9\n01 2 5 slow\n02 1 |\n| #.\n|  #\n03 2 x skip a=1 b=2\n").unwrap();
+		let no_parameters = Parameters::new();
+		let mut parameters = Parameters::new();
+		parameters.set("a", "1");
+		parameters.set("b", "2");
+		assert_eq!(expected, vec![
+			Expected { day: 1, part: 1, answer: "9".to_string(), kind: ExpectedKind::Normal, parameters: no_parameters.clone() },
+			Expected { day: 1, part: 2, answer: "5".to_string(), kind: ExpectedKind::Slow, parameters: no_parameters.clone() },
+			Expected { day: 2, part: 1, answer: "#.\n #\n".to_string(), kind: ExpectedKind::Normal, parameters: no_parameters },
+			Expected { day: 3, part: 2, answer: "x".to_string(), kind: ExpectedKind::Skip, parameters }
+		]);
+
+		assert!(parse_expected("01 1").is_err());
+		assert!(parse_expected("01 1 9 fast").is_err());
+		assert!(parse_expected("01 1 9 a=1 slow").is_err());
+		assert!(parse_expected("01 1 9\n| x").is_err());
+
+		let dir = std::env::temp_dir().join(format!("aoc_commons_expected_{}", std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		std::fs::write(dir.join("input01.txt"), "1\n5\n3\n").unwrap();
+
+		let mut registry = Registry::new();
+		registry.register::<Sum>();
+
+		let table = parse_expected("01 1 9\n01 1 18 factor=2\n01 2 4 slow\n01 2 0 skip\n02 1 1").unwrap();
+		assert_eq!(check_expected(&registry, 2000, &dir, &table, false), vec!["2000 day 02 part 1: not registered".to_string()]);
+		let mismatches = check_expected(&registry, 2000, &dir, &table, true);
+		assert_eq!(mismatches[0], "2000 day 01 part 2: expected \"4\", got \"5\"");
+
+		std::fs::remove_dir_all(&dir).unwrap();
+	}
+
+	#[test]
+	fn test_report() {
+		let line = "add 1 x";
+		let err = crate::match_tokens!(line.split(' '); "add", a: FromStrToTryFromAdapter<u8> {.0}, b: FromStrToTryFromAdapter<u8> {.0})
+			.map_err(|err| err.in_line(line).at_line_number(3))
+			.context("Failed to parse input")
+			.unwrap_err();
+
+		assert_eq!(
+			report(&err),
+			"Failed to parse input: line 3, token 2 \"x\": Failed to parse as u8: invalid digit found in string\n  add 1 x\n        ^"
+		);
+		assert_eq!(report(&anyhow::anyhow!("plain")), "plain");
+	}
+
+	#[test]
+	fn test_parse_selection() {
+		let args = |args: &[&str]| args.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+		let a = args(&["2022", "17", "--part", "2"]);
+		assert_eq!(parse_selection(&a).unwrap(), (2022, Some(17), &a[2 ..]));
+
+		let a = args(&["2022", "all", "--example"]);
+		assert_eq!(parse_selection(&a).unwrap(), (2022, None, &a[2 ..]));
+
+		let a = args(&["2022", "--example"]);
+		assert_eq!(parse_selection(&a).unwrap(), (2022, None, &a[1 ..]));
+
+		assert!(parse_selection(&args(&[])).is_err());
+		assert!(parse_selection(&args(&["twenty"])).is_err());
+		assert!(parse_selection(&args(&["2022", "300"])).is_err());
+	}
+}
@@ -0,0 +1,224 @@
+use std::{
+	fmt,
+	ops::{Add, Range, Sub}
+};
+
+/// Set of values stored as a sorted list of disjoint, non-adjacent half-open ranges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSet<T> {
+	ranges: Vec<Range<T>>
+}
+impl<T: Copy + Ord> RangeSet<T> {
+	pub fn new() -> Self {
+		Self { ranges: Vec::new() }
+	}
+
+	pub fn ranges(&self) -> &[Range<T>] {
+		&self.ranges
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.ranges.is_empty()
+	}
+
+	/// Smallest and largest-plus-one contained values.
+	pub fn bounds(&self) -> Option<Range<T>> {
+		match (self.ranges.first(), self.ranges.last()) {
+			(Some(first), Some(last)) => Some(first.start .. last.end),
+			_ => None
+		}
+	}
+
+	pub fn contains(&self, value: &T) -> bool {
+		let index = self.ranges.partition_point(|r| r.end <= *value);
+
+		self.ranges.get(index).map(|r| r.start <= *value).unwrap_or(false)
+	}
+
+	/// Inserts all values of `range`, merging it with overlapping and adjacent ranges.
+	pub fn insert(&mut self, range: Range<T>) {
+		if range.is_empty() {
+			return;
+		}
+
+		let first = self.ranges.partition_point(|r| r.end < range.start);
+		let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+		let merged = if first < last {
+			self.ranges[first].start.min(range.start) .. self.ranges[last - 1].end.max(range.end)
+		} else {
+			range
+		};
+		self.ranges.splice(first .. last, [merged]);
+	}
+
+	/// Removes all values of `range`, splitting ranges which only partially overlap it.
+	pub fn remove(&mut self, range: Range<T>) {
+		if range.is_empty() {
+			return;
+		}
+
+		let first = self.ranges.partition_point(|r| r.end <= range.start);
+		let last = self.ranges.partition_point(|r| r.start < range.end);
+		if first >= last {
+			return;
+		}
+
+		let before = self.ranges[first].start .. range.start;
+		let after = range.end .. self.ranges[last - 1].end;
+		self.ranges.splice(
+			first .. last,
+			[before, after].into_iter().filter(|r| !r.is_empty())
+		);
+	}
+
+	pub fn union(&self, other: &Self) -> Self {
+		let mut result = self.clone();
+		for range in other.ranges.iter() {
+			result.insert(range.clone());
+		}
+
+		result
+	}
+
+	pub fn intersection(&self, other: &Self) -> Self {
+		let mut ranges = Vec::new();
+
+		let mut left = self.ranges.iter().peekable();
+		let mut right = other.ranges.iter().peekable();
+		while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+			let start = l.start.max(r.start);
+			let end = l.end.min(r.end);
+			if start < end {
+				ranges.push(start .. end);
+			}
+
+			if l.end < r.end {
+				left.next();
+			} else {
+				right.next();
+			}
+		}
+
+		Self { ranges }
+	}
+
+	pub fn difference(&self, other: &Self) -> Self {
+		let mut result = self.clone();
+		for range in other.ranges.iter() {
+			result.remove(range.clone());
+		}
+
+		result
+	}
+
+	/// Iterates the ranges of values between the ranges of this set.
+	pub fn gaps(&self) -> impl Iterator<Item = Range<T>> + '_ {
+		self.ranges.windows(2).map(|w| w[0].end .. w[1].start)
+	}
+}
+impl<T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>> RangeSet<T> {
+	/// Total count of values in this set.
+	pub fn len(&self) -> T {
+		self.ranges.iter().fold(T::default(), |acc, r| acc + (r.end - r.start))
+	}
+}
+impl<T: Copy + Ord> Default for RangeSet<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+impl<T: Copy + Ord> From<Range<T>> for RangeSet<T> {
+	fn from(value: Range<T>) -> Self {
+		let mut me = Self::new();
+		me.insert(value);
+
+		me
+	}
+}
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+	fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+		let mut me = Self::new();
+		for range in iter {
+			me.insert(range);
+		}
+
+		me
+	}
+}
+impl<T: fmt::Display> fmt::Display for RangeSet<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{{")?;
+		for (i, range) in self.ranges.iter().enumerate() {
+			if i > 0 {
+				write!(f, ", ")?;
+			}
+			write!(f, "{}..{}", range.start, range.end)?;
+		}
+		write!(f, "}}")
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::RangeSet;
+
+	#[test]
+	fn test_insert() {
+		let mut set = RangeSet::new();
+		set.insert(10 .. 15);
+		set.insert(0 .. 3);
+		set.insert(5 .. 5);
+		assert_eq!(set.ranges(), &[0 .. 3, 10 .. 15]);
+
+		// adjacent
+		set.insert(3 .. 4);
+		assert_eq!(set.ranges(), &[0 .. 4, 10 .. 15]);
+
+		// spanning multiple
+		set.insert(20 .. 25);
+		set.insert(2 .. 21);
+		assert_eq!(set.bounds(), Some(0 .. 25));
+		assert_eq!(set.ranges().len(), 1);
+
+		assert_eq!(set.len(), 25);
+		assert!(set.contains(&0));
+		assert!(set.contains(&24));
+		assert!(!set.contains(&25));
+		assert!(!set.contains(&-1));
+	}
+
+	#[test]
+	fn test_remove() {
+		let mut set: RangeSet<i32> = [0 .. 10, 20 .. 30].into_iter().collect();
+
+		set.remove(3 .. 5);
+		assert_eq!(set.ranges(), &[0 .. 3, 5 .. 10, 20 .. 30]);
+
+		set.remove(8 .. 25);
+		assert_eq!(set.ranges(), &[0 .. 3, 5 .. 8, 25 .. 30]);
+
+		set.remove(-5 .. 0);
+		set.remove(30 .. 31);
+		assert_eq!(set.ranges(), &[0 .. 3, 5 .. 8, 25 .. 30]);
+
+		set.remove(-100 .. 100);
+		assert!(set.is_empty());
+	}
+
+	#[test]
+	fn test_set_operations() {
+		let a: RangeSet<i32> = [0 .. 5, 10 .. 15].into_iter().collect();
+		let b: RangeSet<i32> = [3 .. 12, 14 .. 20].into_iter().collect();
+
+		assert_eq!(a.union(&b), RangeSet::from(0 .. 20));
+		assert_eq!(a.intersection(&b).ranges(), &[3 .. 5, 10 .. 12, 14 .. 15]);
+		assert_eq!(a.difference(&b).ranges(), &[0 .. 3, 12 .. 14]);
+		assert_eq!(b.difference(&a).ranges(), &[5 .. 10, 15 .. 20]);
+
+		assert_eq!(a.gaps().collect::<Vec<_>>(), vec![5 .. 10]);
+		assert_eq!(a.bounds(), Some(0 .. 15));
+		assert_eq!(RangeSet::from(0 .. 20).difference(&a.union(&b)).len(), 0);
+		assert_eq!(a.to_string(), "{0..5, 10..15}");
+	}
+}
@@ -0,0 +1,250 @@
+use std::{
+	cmp::Ordering,
+	collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+	hash::Hash,
+	ops::Add
+};
+
+/// Produces the states reachable from a state in one step.
+///
+/// For [`bfs`] the items are plain states, for [`dijkstra`] and [`astar`] they are `(state, step_cost)` pairs.
+///
+/// Implemented for any `FnMut(&S) -> impl IntoIterator`.
+pub trait Successors<S> {
+	type Item;
+
+	fn successors(&mut self, state: &S) -> impl IntoIterator<Item = Self::Item>;
+}
+impl<S, I: IntoIterator, F: FnMut(&S) -> I> Successors<S> for F {
+	type Item = I::Item;
+
+	fn successors(&mut self, state: &S) -> impl IntoIterator<Item = Self::Item> {
+		self(state)
+	}
+}
+
+/// Result of a search - costs and parents of all visited states and the reached goal, if any.
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+	visited: HashMap<S, (C, Option<S>)>,
+	goal: Option<S>
+}
+impl<S: Clone + Eq + Hash, C: Copy> Search<S, C> {
+	pub fn goal(&self) -> Option<&S> {
+		self.goal.as_ref()
+	}
+
+	pub fn goal_cost(&self) -> Option<C> {
+		self.goal.as_ref().and_then(|goal| self.cost(goal))
+	}
+
+	pub fn goal_path(&self) -> Option<Vec<S>> {
+		self.goal.as_ref().and_then(|goal| self.path(goal))
+	}
+
+	/// Cost of the best known path to `state`.
+	///
+	/// When the search stopped early this is only final for the goal and states with lower cost.
+	pub fn cost(&self, state: &S) -> Option<C> {
+		self.visited.get(state).map(|(cost, _)| *cost)
+	}
+
+	/// Reconstructs the path from one of the start states to `state`, both inclusive.
+	pub fn path(&self, state: &S) -> Option<Vec<S>> {
+		let mut current = self.visited.get_key_value(state)?;
+
+		let mut path = vec![current.0.clone()];
+		while let Some(parent) = current.1.1.as_ref() {
+			current = self.visited.get_key_value(parent).unwrap();
+			path.push(current.0.clone());
+		}
+		path.reverse();
+
+		Some(path)
+	}
+
+	pub fn visited(&self) -> impl Iterator<Item = (&S, C)> {
+		self.visited.iter().map(|(state, (cost, _))| (state, *cost))
+	}
+}
+
+/// Breadth-first search from all `starts` at once, stopping at the first state for which `is_goal` returns true.
+///
+/// The cost of each state is the number of steps from the nearest start.
+pub fn bfs<S: Clone + Eq + Hash>(
+	starts: impl IntoIterator<Item = S>,
+	mut successors: impl Successors<S, Item = S>,
+	mut is_goal: impl FnMut(&S) -> bool
+) -> Search<S, usize> {
+	let mut visited = HashMap::new();
+	let mut queue = VecDeque::new();
+
+	for start in starts {
+		if let Entry::Vacant(entry) = visited.entry(start.clone()) {
+			entry.insert((0, None));
+			queue.push_back((start, 0));
+		}
+	}
+
+	while let Some((current, steps)) = queue.pop_front() {
+		if is_goal(&current) {
+			return Search { visited, goal: Some(current) };
+		}
+
+		for next in successors.successors(&current) {
+			if let Entry::Vacant(entry) = visited.entry(next.clone()) {
+				entry.insert((steps + 1, Some(current.clone())));
+				queue.push_back((next, steps + 1));
+			}
+		}
+	}
+
+	Search { visited, goal: None }
+}
+
+/// Dijkstra's shortest path search from all `starts` at once, stopping at the first state for which `is_goal` returns true.
+pub fn dijkstra<S: Clone + Eq + Hash, C: Copy + Ord + Default + Add<Output = C>>(
+	starts: impl IntoIterator<Item = S>,
+	successors: impl Successors<S, Item = (S, C)>,
+	is_goal: impl FnMut(&S) -> bool
+) -> Search<S, C> {
+	astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// A* search from all `starts` at once, stopping at the first state for which `is_goal` returns true.
+///
+/// `heuristic` must never overestimate the remaining cost to the goal, otherwise the found path might not be the shortest.
+pub fn astar<S: Clone + Eq + Hash, C: Copy + Ord + Default + Add<Output = C>>(
+	starts: impl IntoIterator<Item = S>,
+	mut successors: impl Successors<S, Item = (S, C)>,
+	mut heuristic: impl FnMut(&S) -> C,
+	mut is_goal: impl FnMut(&S) -> bool
+) -> Search<S, C> {
+	let mut visited: HashMap<S, (C, Option<S>)> = HashMap::new();
+	let mut queue = BinaryHeap::new();
+
+	for start in starts {
+		if let Entry::Vacant(entry) = visited.entry(start.clone()) {
+			entry.insert((C::default(), None));
+			queue.push(QueueEntry { priority: heuristic(&start), cost: C::default(), state: start });
+		}
+	}
+
+	while let Some(QueueEntry { cost, state: current, .. }) = queue.pop() {
+		// stale entry, a cheaper path was found after this one was pushed
+		if visited[&current].0 < cost {
+			continue;
+		}
+
+		if is_goal(&current) {
+			return Search { visited, goal: Some(current) };
+		}
+
+		for (next, step_cost) in successors.successors(&current) {
+			let next_cost = cost + step_cost;
+
+			match visited.entry(next.clone()) {
+				Entry::Occupied(entry) if entry.get().0 <= next_cost => continue,
+				Entry::Occupied(mut entry) => { entry.insert((next_cost, Some(current.clone()))); }
+				Entry::Vacant(entry) => { entry.insert((next_cost, Some(current.clone()))); }
+			}
+
+			queue.push(QueueEntry { priority: next_cost + heuristic(&next), cost: next_cost, state: next });
+		}
+	}
+
+	Search { visited, goal: None }
+}
+
+/// Min-heap entry for [`BinaryHeap`], ordered only by priority.
+struct QueueEntry<S, C> {
+	priority: C,
+	cost: C,
+	state: S
+}
+impl<S, C: Ord> PartialEq for QueueEntry<S, C> {
+	fn eq(&self, other: &Self) -> bool {
+		self.priority == other.priority
+	}
+}
+impl<S, C: Ord> Eq for QueueEntry<S, C> {}
+impl<S, C: Ord> PartialOrd for QueueEntry<S, C> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+impl<S, C: Ord> Ord for QueueEntry<S, C> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		other.priority.cmp(&self.priority)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::geometry::{Grid2, Point};
+
+	use super::{bfs, dijkstra, astar};
+
+	type Point2 = Point<2>;
+
+	const HEIGHTMAP: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi\n";
+
+	fn heightmap() -> (Grid2<u8>, Point2, Point2) {
+		let (grid, [start, end]) = Grid2::<char>::parse_markers(HEIGHTMAP, ['S', 'E']).unwrap();
+		let heights = grid.bounding_box().points_iter().map(|p| match grid.get(p).unwrap() {
+			'S' => 0,
+			'E' => 25,
+			c => *c as u8 - b'a'
+		}).collect();
+
+		(Grid2::new(heights, grid.bounding_box()).unwrap(), start, end)
+	}
+
+	#[test]
+	fn test_bfs_grid() {
+		let (grid, start, end) = heightmap();
+		let climb = |p: &Point2| {
+			let height = *grid.get(*p).unwrap();
+			grid.neighbors_orthogonal(*p).filter(move |(_, h)| **h <= height + 1).map(|(p, _)| p)
+		};
+
+		let search = bfs([start], climb, |p| *p == end);
+		assert_eq!(search.goal_cost(), Some(31));
+
+		let path = search.goal_path().unwrap();
+		assert_eq!(path.len(), 32);
+		assert_eq!(path.first(), Some(&start));
+		assert_eq!(path.last(), Some(&end));
+		assert!(path.windows(2).all(|w| (w[1] - w[0]).length_manhattan() == 1));
+
+		let starts = grid.bounding_box().points_iter().filter(|p| *grid.get(*p).unwrap() == 0);
+		assert_eq!(bfs(starts, climb, |p| *p == end).goal_cost(), Some(29));
+
+		// exhaustive search without a goal
+		let search = bfs([end], |p: &Point2| grid.neighbors_orthogonal(*p).map(|(p, _)| p).collect::<Vec<_>>(), |_| false);
+		assert_eq!(search.goal(), None);
+		assert_eq!(search.visited().count(), grid.bounding_box().area());
+		assert_eq!(search.cost(&start), Some((end - start).length_manhattan() as usize));
+	}
+
+	#[test]
+	fn test_weighted_grid() {
+		let grid = Grid2::<char>::parse("1163751\n1381373\n2136511\n3694931\n7463417\n").unwrap();
+		let end = grid.bounding_box().max - Point2::new(1, 1);
+		let risk = |p: &Point2| grid.neighbors_orthogonal(*p).map(|(p, c)| (p, c.to_digit(10).unwrap())).collect::<Vec<_>>();
+
+		let search = dijkstra([Point2::ZERO], risk, |p| *p == end);
+		let expected = search.goal_cost().unwrap();
+		assert_eq!(expected, 28);
+
+		let path = search.goal_path().unwrap();
+		let path_cost: u32 = path.iter().skip(1).map(|p| grid.get(*p).unwrap().to_digit(10).unwrap()).sum();
+		assert_eq!(path_cost, expected);
+
+		let search = astar([Point2::ZERO], risk, |p| (end - *p).length_manhattan() as u32, |p| *p == end);
+		assert_eq!(search.goal_cost(), Some(expected));
+
+		let search = dijkstra([Point2::ZERO], risk, |_| false);
+		assert_eq!(search.cost(&end), Some(expected));
+	}
+}